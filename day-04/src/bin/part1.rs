@@ -1,6 +1,7 @@
 use std::{env, fs};
 
 use day_04::card::Card;
+use day_04::scoring::{total_score, Doubling};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -9,10 +10,7 @@ fn main() {
     println!("Reading from file '{}'", file_path);
 
     let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let mut sum = 0;
-    contents
-        .lines()
-        .into_iter()
-        .for_each(|line| sum += Card::from_line(line).get_score());
+    let cards: Vec<Card> = contents.lines().map(Card::from_line).collect();
+    let sum = total_score(&cards, &Doubling);
     println!("Result = {}", sum);
 }
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, env, fs};
 
 use day_04::card::Card;
+use day_04::validation::{validate, CopyPolicy};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -8,34 +9,59 @@ fn main() {
     let file_path = &args[1];
     println!("Reading from file '{}'", file_path);
 
+    let policy = match args.get(2).map(String::as_str) {
+        Some("--clamp") => CopyPolicy::Clamp,
+        _ => CopyPolicy::Error,
+    };
+
     let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
+    let issues = validate(&contents, policy);
+    issues.iter().for_each(|issue| eprintln!("{}", issue));
+    // Only stop for issues the policy can not count cards through
+    let no_of_fatal_issues = issues.iter().filter(|issue| issue.is_fatal(policy)).count();
+    if no_of_fatal_issues > 0 {
+        println!(
+            "Input failed validation with {} issue(s)",
+            no_of_fatal_issues
+        );
+        return;
+    }
+
     let mut cards = HashMap::new();
-    contents.lines().into_iter().for_each(|line: &str| {
-        let card = Card::from_line(line);
-        _ = cards.insert(card.id, card)
-    });
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .for_each(|line: &str| {
+            let card = Card::from_line(line);
+            _ = cards.insert(card.id, card)
+        });
 
+    let last_id = *cards.keys().max().expect("Input had no cards");
     let mut total_cards: Vec<&Card> = Vec::new();
     for card in cards.values() {
-        get_scratchcards(card, &cards)
+        get_scratchcards(card, &cards, last_id, policy)
             .iter()
-            .for_each(|&c| _ = total_cards.push(c));
+            .for_each(|&c| total_cards.push(c));
     }
     println!("Result = {}", total_cards.len());
 }
 
-fn get_scratchcards<'a>(card: &'a Card, cards: &'a HashMap<u8, Card>) -> Vec<&'a Card> {
+fn get_scratchcards<'a>(
+    card: &'a Card,
+    cards: &'a HashMap<u8, Card>,
+    last_id: u8,
+    policy: CopyPolicy,
+) -> Vec<&'a Card> {
     let mut total_cards = vec![];
     total_cards.push(card);
-    let matches = card.get_no_of_winning_numbers();
-
-    let mut i = 0;
-    while i < matches {
-        i += 1;
-        let copied_card = cards
-            .get(&(card.id + i))
-            .expect("Id exceeded past end of table");
-        total_cards.append(&mut get_scratchcards(copied_card, cards));
+    let copied_ids = match policy.copied_ids(card, last_id) {
+        Ok(ids) => ids,
+        Err(err) => panic!("{}", err),
+    };
+
+    for id in copied_ids {
+        let copied_card = cards.get(&id).expect("Id missing from table");
+        total_cards.append(&mut get_scratchcards(copied_card, cards, last_id, policy));
     }
 
     total_cards
@@ -43,9 +69,9 @@ fn get_scratchcards<'a>(card: &'a Card, cards: &'a HashMap<u8, Card>) -> Vec<&'a
 
 #[cfg(test)]
 mod tests {
-    use std::collections::{HashMap, HashSet};
+    use std::collections::HashMap;
 
-    use super::Card;
+    use super::{Card, CopyPolicy};
 
     fn cards() -> HashMap<u8, Card> {
         let mut cards = HashMap::new();
@@ -70,10 +96,25 @@ mod tests {
         let cards = cards();
         let mut total_cards: Vec<&Card> = Vec::new();
         for card in cards.values() {
-            super::get_scratchcards(card, &cards)
+            super::get_scratchcards(card, &cards, 6, CopyPolicy::Error)
                 .iter()
-                .for_each(|&c| _ = total_cards.push(c));
+                .for_each(|&c| total_cards.push(c));
         }
         assert_eq!(30, total_cards.len());
     }
+
+    #[test]
+    fn can_clamp_copies_past_end() {
+        let mut cards = cards();
+        cards.remove(&6);
+        cards.remove(&5);
+        let mut total_cards: Vec<&Card> = Vec::new();
+        for card in cards.values() {
+            super::get_scratchcards(card, &cards, 4, CopyPolicy::Clamp)
+                .iter()
+                .for_each(|&c| total_cards.push(c));
+        }
+        // Card 1 copies 2..=4, card 2 copies 3 and 4, card 3 copies 4 and card 4 copies nothing
+        assert_eq!(15, total_cards.len());
+    }
 }
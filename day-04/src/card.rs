@@ -1,56 +1,69 @@
-use std::collections::HashSet;
 use std::hash::Hash;
 
-#[derive(Debug, PartialEq, Eq)]
+use crate::number_set::NumberSet;
+use crate::scoring::ScoringRule;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Card {
     pub id: u8,
-    pub winning_numbers: HashSet<u8>,
-    pub player_numbers: HashSet<u8>,
+    pub winning_numbers: NumberSet,
+    pub player_numbers: NumberSet,
 }
 
 impl Card {
     pub fn from_line(line: &str) -> Self {
-        let mut line = &line[4..]; // Remove 'Card'
-        line = line.trim_start();
-        let colon_index = line.find(':').expect("Colon not found");
-        let pipe_index = line.find('|').expect("Pipe not found");
-
-        let id = &line[..colon_index];
-        let id = id.parse().expect("Could not parse ID");
-
-        let winning_numbers = &line[colon_index + 1..pipe_index].trim();
-        let winning_numbers = winning_numbers
-            .split(' ')
-            .filter(|val| !val.is_empty())
-            .map(|val| val.parse::<u8>().expect("Could not parse winning number"))
-            .collect();
-
-        let player_numbers = &line[pipe_index + 1..].trim();
-        let player_numbers = player_numbers
-            .split(' ')
-            .filter(|val| !val.is_empty())
-            .map(|val| val.parse::<u8>().expect("Could not parse player number"))
-            .collect();
-
+        let (id, winning_numbers, player_numbers) = match Self::parse_line(line) {
+            Ok(parsed) => parsed,
+            Err(err) => panic!("{}", err),
+        };
         Card {
             id,
-            winning_numbers,
-            player_numbers,
+            winning_numbers: winning_numbers.into_iter().collect(),
+            player_numbers: player_numbers.into_iter().collect(),
         }
     }
 
-    pub fn get_no_of_winning_numbers(&self) -> u8 {
+    /// Parses a line into its id and number lists, keeping duplicates and list order.
+    pub(crate) fn parse_line(line: &str) -> Result<(u8, Vec<u8>, Vec<u8>), String> {
+        let card = line
+            .trim_start()
+            .strip_prefix("Card")
+            .ok_or(format!("Line '{}' does not start with 'Card'", line))?;
+        let (id, numbers) = card
+            .split_once(':')
+            .ok_or(format!("Line '{}' has no colon after its id", line))?;
+        let (winning_numbers, player_numbers) = numbers
+            .split_once('|')
+            .ok_or(format!("Line '{}' has no pipe between its lists", line))?;
+
+        let id = id
+            .trim()
+            .parse()
+            .map_err(|err| format!("Could not parse ID '{}', {}", id.trim(), err))?;
+        let parse_numbers = |numbers: &str, list: &str| {
+            numbers
+                .split_whitespace()
+                .map(|val| {
+                    val.parse::<u8>().map_err(|err| {
+                        format!("Could not parse {} number '{}', {}", list, val, err)
+                    })
+                })
+                .collect::<Result<Vec<u8>, String>>()
+        };
+        let winning_numbers = parse_numbers(winning_numbers, "winning")?;
+        let player_numbers = parse_numbers(player_numbers, "player")?;
+
+        Ok((id, winning_numbers, player_numbers))
+    }
+
+    /// Up to 256, as each list holds distinct `u8` numbers.
+    pub fn get_no_of_winning_numbers(&self) -> u32 {
         self.player_numbers
-            .intersection(&self.winning_numbers)
-            .count() as u8
+            .intersection_count(&self.winning_numbers) as u32
     }
 
-    pub fn get_score(&self) -> u32 {
-        let no_of_winning_numbers = self.get_no_of_winning_numbers();
-        if no_of_winning_numbers == 0 {
-            return 0;
-        }
-        2_u32.pow(no_of_winning_numbers as u32 - 1)
+    pub fn get_score<R: ScoringRule + ?Sized>(&self, rule: &R) -> u64 {
+        rule.score(self.get_no_of_winning_numbers())
     }
 }
 
@@ -63,21 +76,22 @@ impl Hash for Card {
 #[cfg(test)]
 mod a_card {
     use rstest::rstest;
-    use std::collections::HashSet;
 
     use super::Card;
+    use crate::number_set::NumberSet;
+    use crate::scoring::Doubling;
 
     #[test]
     fn can_be_created_from_line() {
         let line = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
-        let mut winning_numbers = HashSet::new();
+        let mut winning_numbers = NumberSet::new();
         winning_numbers.insert(41);
         winning_numbers.insert(48);
         winning_numbers.insert(83);
         winning_numbers.insert(86);
         winning_numbers.insert(17);
 
-        let mut player_numbers = HashSet::new();
+        let mut player_numbers = NumberSet::new();
         player_numbers.insert(83);
         player_numbers.insert(86);
         player_numbers.insert(6);
@@ -104,8 +118,32 @@ mod a_card {
     #[case("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83", 1)]
     #[case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36", 0)]
     #[case("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11", 0)]
-    fn can_get_its_score(#[case] line: &str, #[case] score: u32) {
+    fn can_get_its_score(#[case] line: &str, #[case] score: u64) {
         let card = Card::from_line(line);
-        assert_eq!(score, card.get_score());
+        assert_eq!(score, card.get_score(&Doubling));
+    }
+
+    #[test]
+    fn can_count_every_number_matching() {
+        let numbers = (0..=255)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let card = Card::from_line(&format!("Card 1: {} | {}", numbers, numbers));
+        assert_eq!(256, card.get_no_of_winning_numbers());
+        assert_eq!(u64::MAX, card.get_score(&Doubling));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("Car")]
+    #[case("Cärd 1: 1 | 2")]
+    #[case("Card 1 1 2 | 3")]
+    #[case("Card 1: 1 2 3")]
+    #[case("Card x: 1 | 2")]
+    #[case("Card 1: 1 300 | 2")]
+    #[case("Card 1: 1 | 2 -3")]
+    fn rejects_malformed_lines(#[case] line: &str) {
+        assert!(Card::parse_line(line).is_err());
     }
 }
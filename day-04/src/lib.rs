@@ -1 +1,5 @@
 pub mod card;
+pub mod number_set;
+pub mod probability;
+pub mod scoring;
+pub mod validation;
@@ -0,0 +1,147 @@
+/// Fixed-width set of small numbers backed by `WORDS` 128-bit words.
+///
+/// Puzzle numbers are all below 100 so a single word is enough for them, but card numbers are
+/// parsed as `u8`, so cards use [`NumberSet`] which covers the whole `u8` range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitSet<const WORDS: usize> {
+    words: [u128; WORDS],
+}
+
+pub type NumberSet = BitSet<2>;
+
+impl<const WORDS: usize> BitSet<WORDS> {
+    pub const CAPACITY: usize = WORDS * u128::BITS as usize;
+
+    pub fn new() -> Self {
+        BitSet { words: [0; WORDS] }
+    }
+
+    /// Returns whether the value was newly inserted.
+    pub fn insert(&mut self, value: usize) -> bool {
+        assert!(
+            value < Self::CAPACITY,
+            "Value {} exceeds bitset capacity {}",
+            value,
+            Self::CAPACITY
+        );
+        let (word, bit) = Self::position(value);
+        let was_present = self.words[word] & bit != 0;
+        self.words[word] |= bit;
+        !was_present
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        if value >= Self::CAPACITY {
+            return false;
+        }
+        let (word, bit) = Self::position(value);
+        self.words[word] & bit != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    pub fn intersection_count(&self, other: &Self) -> usize {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..Self::CAPACITY).filter(move |value| self.contains(*value))
+    }
+
+    fn position(value: usize) -> (usize, u128) {
+        let bits = u128::BITS as usize;
+        (value / bits, 1 << (value % bits))
+    }
+}
+
+impl<const WORDS: usize> Default for BitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> FromIterator<u8> for BitSet<WORDS> {
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut set = Self::new();
+        iter.into_iter()
+            .for_each(|value| _ = set.insert(value as usize));
+        set
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for BitSet<WORDS> {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut set = Self::new();
+        iter.into_iter().for_each(|value| _ = set.insert(value));
+        set
+    }
+}
+
+#[cfg(test)]
+mod a_bitset {
+    use std::collections::HashSet;
+
+    use rstest::rstest;
+
+    use super::{BitSet, NumberSet};
+
+    #[test]
+    fn can_insert_and_query_values() {
+        let mut set = NumberSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(0));
+        assert!(set.insert(99));
+        assert!(set.insert(255));
+        assert!(!set.insert(99));
+
+        assert_eq!(3, set.len());
+        assert!(set.contains(99));
+        assert!(!set.contains(98));
+        assert!(!set.contains(1000));
+        assert_eq!(vec![0, 99, 255], set.iter().collect::<Vec<usize>>());
+    }
+
+    #[rstest]
+    #[case(vec![41, 48, 83, 86, 17], vec![83, 86, 6, 31, 17, 9, 48, 53])]
+    #[case(vec![1, 127, 128, 200], vec![127, 128, 201])]
+    #[case(vec![], vec![5])]
+    fn counts_the_same_intersection_as_a_hashset(#[case] a: Vec<u8>, #[case] b: Vec<u8>) {
+        let expected = a
+            .iter()
+            .collect::<HashSet<_>>()
+            .intersection(&b.iter().collect())
+            .count();
+
+        let a: NumberSet = a.into_iter().collect();
+        let b: NumberSet = b.into_iter().collect();
+        assert_eq!(expected, a.intersection_count(&b));
+    }
+
+    #[test]
+    fn can_be_wider_than_a_u8() {
+        let set: BitSet<8> = [3_usize, 500, 1023].into_iter().collect();
+        assert_eq!(1024, BitSet::<8>::CAPACITY);
+        assert_eq!(3, set.len());
+        assert!(set.contains(500));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_when_value_exceeds_capacity() {
+        let mut set = BitSet::<1>::new();
+        set.insert(128);
+    }
+}
@@ -0,0 +1,308 @@
+use rand::rngs::StdRng;
+use rand::seq::index::sample;
+use rand::SeedableRng;
+
+use crate::card::Card;
+use crate::number_set::NumberSet;
+use crate::scoring::ScoringRule;
+use crate::validation::CopyPolicy;
+
+/// A scratchcard game where both lists are drawn without repeats from the numbers `0..pool_size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardDesign {
+    pool_size: usize,
+    winning_size: usize,
+    player_size: usize,
+}
+
+/// Expected outcomes of a design, for a table of `no_of_cards` cards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    /// Probability of each number of matches, indexed by the number of matches.
+    pub match_distribution: Vec<f64>,
+    pub expected_score: f64,
+    /// Expected copies a single card wins, ignoring the end of the table.
+    pub expected_copies_won: f64,
+    /// Expected part 2 result, with copies past the end of the table clamped.
+    pub expected_total_cards: f64,
+}
+
+impl CardDesign {
+    pub fn new(pool_size: usize, winning_size: usize, player_size: usize) -> Result<Self, String> {
+        if pool_size > NumberSet::CAPACITY {
+            return Err(format!(
+                "Pool size {} is larger than the {} numbers a card can hold",
+                pool_size,
+                NumberSet::CAPACITY
+            ));
+        }
+        if winning_size > pool_size || player_size > pool_size {
+            return Err(format!(
+                "Lists of {} winning and {} player numbers can not be drawn from a pool of {}",
+                winning_size, player_size, pool_size
+            ));
+        }
+        Ok(CardDesign {
+            pool_size,
+            winning_size,
+            player_size,
+        })
+    }
+
+    pub fn max_matches(&self) -> usize {
+        self.winning_size.min(self.player_size)
+    }
+
+    /// The hypergeometric distribution of `Card::get_no_of_winning_numbers`.
+    pub fn match_distribution(&self) -> Vec<f64> {
+        let total = binomial(self.pool_size, self.player_size);
+        (0..=self.max_matches())
+            .map(|matches| {
+                let hits = binomial(self.winning_size, matches);
+                let misses = binomial(
+                    self.pool_size - self.winning_size,
+                    self.player_size - matches,
+                );
+                hits * misses / total
+            })
+            .collect()
+    }
+
+    pub fn estimate<R: ScoringRule + ?Sized>(&self, rule: &R, no_of_cards: usize) -> Estimate {
+        let match_distribution = self.match_distribution();
+        let expected_score = match_distribution
+            .iter()
+            .enumerate()
+            .map(|(matches, p)| p * rule.score(matches as u32) as f64)
+            .sum();
+        let expected_copies_won = match_distribution
+            .iter()
+            .enumerate()
+            .map(|(matches, p)| p * matches as f64)
+            .sum();
+
+        // P(at least j matches), so card i copies card i + j with this probability
+        let mut at_least = vec![0.0; match_distribution.len() + 1];
+        for matches in (0..match_distribution.len()).rev() {
+            at_least[matches] = at_least[matches + 1] + match_distribution[matches];
+        }
+        // Expected instances of each card, counted back from the last card. As each card's
+        // matches are independent of the cards after it, expectations can be chained.
+        let mut instances = vec![0.0; no_of_cards];
+        for i in (0..no_of_cards).rev() {
+            instances[i] = 1.0
+                + (1..at_least.len())
+                    .take_while(|j| i + j < no_of_cards)
+                    .map(|j| at_least[j] * instances[i + j])
+                    .sum::<f64>();
+        }
+
+        Estimate {
+            match_distribution,
+            expected_score,
+            expected_copies_won,
+            expected_total_cards: instances.iter().sum(),
+        }
+    }
+
+    /// Estimates the same values as [`CardDesign::estimate`] by dealing `tables` random tables
+    /// of `no_of_cards` cards, seeded so runs can be repeated.
+    pub fn simulate<R: ScoringRule + ?Sized>(
+        &self,
+        rule: &R,
+        no_of_cards: usize,
+        tables: usize,
+        seed: u64,
+    ) -> Result<Estimate, String> {
+        // Card ids are stored as a u8
+        if no_of_cards == 0 || no_of_cards > u8::MAX as usize {
+            return Err(format!(
+                "Tables of {} cards can not be simulated, expected 1 to {}",
+                no_of_cards,
+                u8::MAX
+            ));
+        }
+        if tables == 0 {
+            return Err("Can not simulate without any tables".to_string());
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut match_counts = vec![0_u64; self.max_matches() + 1];
+        let mut total_score = 0_u64;
+        let mut total_cards = 0_u64;
+
+        for _ in 0..tables {
+            let cards: Vec<Card> = (1..=no_of_cards as u8)
+                .map(|id| Card {
+                    id,
+                    winning_numbers: sample(&mut rng, self.pool_size, self.winning_size)
+                        .into_iter()
+                        .collect(),
+                    player_numbers: sample(&mut rng, self.pool_size, self.player_size)
+                        .into_iter()
+                        .collect(),
+                })
+                .collect();
+            for card in cards.iter() {
+                match_counts[card.get_no_of_winning_numbers() as usize] += 1;
+                total_score = total_score.saturating_add(card.get_score(rule));
+            }
+            total_cards += count_scratchcards(&cards);
+        }
+
+        let samples = (tables * no_of_cards) as f64;
+        let match_distribution: Vec<f64> = match_counts
+            .iter()
+            .map(|count| *count as f64 / samples)
+            .collect();
+        let expected_copies_won = match_distribution
+            .iter()
+            .enumerate()
+            .map(|(matches, p)| p * matches as f64)
+            .sum();
+        Ok(Estimate {
+            match_distribution,
+            expected_score: total_score as f64 / samples,
+            expected_copies_won,
+            expected_total_cards: total_cards as f64 / tables as f64,
+        })
+    }
+}
+
+/// Part 2 total for a table whose ids run from 1 to `cards.len()`.
+fn count_scratchcards(cards: &[Card]) -> u64 {
+    let last_id = cards.len() as u8;
+    let mut instances = vec![1_u64; cards.len()];
+    for card in cards.iter() {
+        let copies = instances[card.id as usize - 1];
+        let copied_ids = CopyPolicy::Clamp
+            .copied_ids(card, last_id)
+            .expect("Clamped copies can not overflow");
+        for id in copied_ids {
+            instances[id as usize - 1] += copies;
+        }
+    }
+    instances.iter().sum()
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    let k = k.min(n - k);
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+#[cfg(test)]
+mod a_card_design {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::scoring::{Doubling, Linear};
+
+    #[rstest]
+    #[case(5, 2, 2, vec![0.3, 0.6, 0.1])]
+    #[case(4, 1, 3, vec![0.25, 0.75])]
+    #[case(3, 3, 3, vec![0.0, 0.0, 0.0, 1.0])]
+    fn can_get_the_match_distribution(
+        #[case] pool_size: usize,
+        #[case] winning_size: usize,
+        #[case] player_size: usize,
+        #[case] expected: Vec<f64>,
+    ) {
+        let design = CardDesign::new(pool_size, winning_size, player_size).unwrap();
+        let distribution = design.match_distribution();
+        assert_eq!(expected.len(), distribution.len());
+        for (expected, actual) in expected.iter().zip(distribution.iter()) {
+            assert!((expected - actual).abs() < 1e-12, "{:?}", distribution);
+        }
+    }
+
+    #[test]
+    fn can_get_expected_totals_for_a_certain_design() {
+        // Every card matches exactly once, so card i is held n - i + 1 times
+        let design = CardDesign::new(2, 1, 2).unwrap();
+        let estimate = design.estimate(&Doubling, 4);
+        assert_eq!(1.0, estimate.expected_score);
+        assert_eq!(1.0, estimate.expected_copies_won);
+        assert_eq!(10.0, estimate.expected_total_cards);
+    }
+
+    #[rstest]
+    #[case(100, 50, 50, 50)]
+    #[case(256, 256, 256, 256)]
+    fn can_estimate_designs_with_many_matches(
+        #[case] pool_size: usize,
+        #[case] winning_size: usize,
+        #[case] player_size: usize,
+        #[case] max_matches: usize,
+    ) {
+        let design = CardDesign::new(pool_size, winning_size, player_size).unwrap();
+        assert_eq!(max_matches, design.max_matches());
+        let estimate = design.estimate(&Doubling, 10);
+        assert_eq!(max_matches + 1, estimate.match_distribution.len());
+        assert!(estimate.expected_score.is_finite());
+    }
+
+    #[test]
+    fn scores_every_match_when_all_numbers_are_drawn() {
+        let design = CardDesign::new(256, 256, 256).unwrap();
+        let estimate = design.estimate(
+            &Linear {
+                points_per_match: 2,
+            },
+            1,
+        );
+        assert_eq!(512.0, estimate.expected_score);
+        assert_eq!(256.0, estimate.expected_copies_won);
+    }
+
+    #[rstest]
+    #[case(0, 1, 1)]
+    #[case(5, 6, 1)]
+    #[case(300, 10, 10)]
+    fn rejects_impossible_designs(
+        #[case] pool_size: usize,
+        #[case] winning_size: usize,
+        #[case] player_size: usize,
+    ) {
+        assert!(CardDesign::new(pool_size, winning_size, player_size).is_err());
+    }
+
+    #[rstest]
+    #[case(0, 10)]
+    #[case(256, 10)]
+    #[case(20, 0)]
+    fn can_not_simulate_unsupported_tables(#[case] no_of_cards: usize, #[case] tables: usize) {
+        let design = CardDesign::new(100, 10, 25).unwrap();
+        assert!(design.simulate(&Doubling, no_of_cards, tables, 4).is_err());
+    }
+
+    #[test]
+    fn is_confirmed_by_simulation() {
+        let design = CardDesign::new(100, 10, 25).unwrap();
+        let analytical = design.estimate(&Doubling, 20);
+        let simulated = design.simulate(&Doubling, 20, 5_000, 4).unwrap();
+
+        for (a, s) in analytical
+            .match_distribution
+            .iter()
+            .zip(simulated.match_distribution.iter())
+        {
+            assert!((a - s).abs() < 0.01, "{} vs {}", a, s);
+        }
+        let relative_error = |a: f64, s: f64| (a - s).abs() / a;
+        assert!(relative_error(analytical.expected_score, simulated.expected_score) < 0.03);
+        assert!(
+            relative_error(
+                analytical.expected_copies_won,
+                simulated.expected_copies_won
+            ) < 0.02
+        );
+        assert!(
+            relative_error(
+                analytical.expected_total_cards,
+                simulated.expected_total_cards
+            ) < 0.02
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::card::Card;
+
+/// Scores a card from its number of matches. Scores too large for a `u64` saturate at `u64::MAX`.
+pub trait ScoringRule {
+    fn score(&self, no_of_winning_numbers: u32) -> u64;
+}
+
+/// The puzzle's rule - one point for the first match, doubled for every match after.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Doubling;
+
+impl ScoringRule for Doubling {
+    fn score(&self, no_of_winning_numbers: u32) -> u64 {
+        if no_of_winning_numbers == 0 {
+            return 0;
+        }
+        2_u64.saturating_pow(no_of_winning_numbers - 1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Linear {
+    pub points_per_match: u64,
+}
+
+impl ScoringRule for Linear {
+    fn score(&self, no_of_winning_numbers: u32) -> u64 {
+        self.points_per_match
+            .saturating_mul(no_of_winning_numbers as u64)
+    }
+}
+
+/// Pays `payouts[n]` for `n` matches. Match counts past the end of the table pay the last entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayoutTable {
+    payouts: Vec<u64>,
+}
+
+impl PayoutTable {
+    pub fn new(payouts: Vec<u64>) -> Self {
+        PayoutTable { payouts }
+    }
+
+    pub fn payouts(&self) -> &[u64] {
+        &self.payouts
+    }
+}
+
+impl ScoringRule for PayoutTable {
+    fn score(&self, no_of_winning_numbers: u32) -> u64 {
+        match self.payouts.get(no_of_winning_numbers as usize) {
+            Some(payout) => *payout,
+            None => self.payouts.last().copied().unwrap_or(0),
+        }
+    }
+}
+
+impl<F> ScoringRule for F
+where
+    F: Fn(u32) -> u64,
+{
+    fn score(&self, no_of_winning_numbers: u32) -> u64 {
+        self(no_of_winning_numbers)
+    }
+}
+
+/// The sum of the cards' scores, saturating at `u64::MAX`.
+pub fn total_score<'a, R>(cards: impl IntoIterator<Item = &'a Card>, rule: &R) -> u64
+where
+    R: ScoringRule + ?Sized,
+{
+    cards
+        .into_iter()
+        .map(|card| card.get_score(rule))
+        .fold(0, u64::saturating_add)
+}
+
+#[cfg(test)]
+mod a_scoring_rule {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(1, 1)]
+    #[case(2, 2)]
+    #[case(4, 8)]
+    #[case(33, 1 << 32)]
+    #[case(64, 1 << 63)]
+    #[case(65, u64::MAX)]
+    #[case(256, u64::MAX)]
+    fn can_double(#[case] matches: u32, #[case] score: u64) {
+        assert_eq!(score, Doubling.score(matches));
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(1, 3)]
+    #[case(4, 12)]
+    #[case(200, 600)]
+    fn can_be_linear(#[case] matches: u32, #[case] score: u64) {
+        let rule = Linear {
+            points_per_match: 3,
+        };
+        assert_eq!(score, rule.score(matches));
+    }
+
+    #[test]
+    fn saturates_linear_scores_too_large_for_a_u64() {
+        let rule = Linear {
+            points_per_match: u64::MAX / 2,
+        };
+        assert_eq!(u64::MAX - 1, rule.score(2));
+        assert_eq!(u64::MAX, rule.score(33));
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(2, 5)]
+    #[case(3, 50)]
+    #[case(10, 50)]
+    #[case(256, 50)]
+    fn can_use_a_payout_table(#[case] matches: u32, #[case] score: u64) {
+        let rule = PayoutTable::new(vec![0, 1, 5, 50]);
+        assert_eq!(score, rule.score(matches));
+    }
+
+    #[test]
+    fn can_use_a_closure() {
+        let rule = |matches: u32| matches as u64 * matches as u64;
+        assert_eq!(16, rule.score(4));
+    }
+
+    #[test]
+    fn can_total_the_example_cards() {
+        let cards: Vec<Card> = [
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ]
+        .into_iter()
+        .map(Card::from_line)
+        .collect();
+
+        assert_eq!(13, total_score(&cards, &Doubling));
+        assert_eq!(
+            9,
+            total_score(
+                &cards,
+                &Linear {
+                    points_per_match: 1
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn saturates_totals_too_large_for_a_u64() {
+        let numbers = (0..40).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+        let line = format!("Card 1: {} | {}", numbers, numbers);
+        let cards = vec![Card::from_line(&line); 3];
+        assert_eq!(1 << 39, cards[0].get_score(&Doubling));
+        assert_eq!(3 << 39, total_score(&cards, &Doubling));
+        let rule = |_: u32| u64::MAX;
+        assert_eq!(u64::MAX, total_score(&cards, &rule));
+    }
+}
@@ -0,0 +1,401 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::card::Card;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberList {
+    Winning,
+    Player,
+}
+
+impl fmt::Display for NumberList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberList::Winning => write!(f, "winning"),
+            NumberList::Player => write!(f, "player"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// A line that could not be parsed as a card, numbered from 1.
+    MalformedLine {
+        line_number: usize,
+        reason: String,
+    },
+    DuplicateNumber {
+        card_id: u8,
+        list: NumberList,
+        number: u8,
+    },
+    RepeatedId {
+        card_id: u8,
+    },
+    IdGap {
+        expected: u8,
+        found: u8,
+    },
+    MismatchedListLength {
+        card_id: u8,
+        list: NumberList,
+        expected: usize,
+        found: usize,
+    },
+    CopiesPastEnd {
+        card_id: u8,
+        last_copied_id: u16,
+        last_id: u8,
+    },
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::MalformedLine {
+                line_number,
+                reason,
+            } => write!(f, "Line {} is malformed, {}", line_number, reason),
+            ValidationIssue::DuplicateNumber {
+                card_id,
+                list,
+                number,
+            } => write!(
+                f,
+                "Card {} has duplicate {} number {}",
+                card_id, list, number
+            ),
+            ValidationIssue::RepeatedId { card_id } => {
+                write!(f, "Card {} appears more than once", card_id)
+            }
+            ValidationIssue::IdGap { expected, found } => {
+                write!(f, "Expected card {} but found card {}", expected, found)
+            }
+            ValidationIssue::MismatchedListLength {
+                card_id,
+                list,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Card {} has {} {} numbers, expected {}",
+                card_id, found, list, expected
+            ),
+            ValidationIssue::CopiesPastEnd {
+                card_id,
+                last_copied_id,
+                last_id,
+            } => write!(
+                f,
+                "Card {} wins copies up to card {}, past the last card {}",
+                card_id, last_copied_id, last_id
+            ),
+        }
+    }
+}
+
+impl ValidationIssue {
+    /// Whether the issue stops the cards being counted under `policy`. Duplicate numbers and
+    /// lists of different lengths still leave every card countable.
+    pub fn is_fatal(&self, policy: CopyPolicy) -> bool {
+        match self {
+            ValidationIssue::DuplicateNumber { .. }
+            | ValidationIssue::MismatchedListLength { .. } => false,
+            ValidationIssue::CopiesPastEnd { .. } => policy == CopyPolicy::Error,
+            ValidationIssue::MalformedLine { .. }
+            | ValidationIssue::RepeatedId { .. }
+            | ValidationIssue::IdGap { .. } => true,
+        }
+    }
+}
+
+/// What to do when a card wins copies of cards past the end of the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CopyPolicy {
+    /// Treat the copies as an error, as the puzzle promises they never happen.
+    #[default]
+    Error,
+    /// Only copy the cards that exist, ignoring the rest.
+    Clamp,
+}
+
+impl CopyPolicy {
+    /// Ids of the cards that `card` wins copies of, given the last id in the table.
+    pub fn copied_ids(
+        &self,
+        card: &Card,
+        last_id: u8,
+    ) -> Result<RangeInclusive<u8>, ValidationIssue> {
+        let no_of_winning_numbers = card.get_no_of_winning_numbers();
+        if no_of_winning_numbers == 0 {
+            return Ok(no_copies());
+        }
+        // At most 255 + 256, as a card matches at most 256 numbers
+        let last_copied_id = card.id as u16 + no_of_winning_numbers as u16;
+        if last_copied_id <= last_id as u16 {
+            return Ok(card.id + 1..=last_copied_id as u8);
+        }
+        match self {
+            CopyPolicy::Error => Err(ValidationIssue::CopiesPastEnd {
+                card_id: card.id,
+                last_copied_id,
+                last_id,
+            }),
+            CopyPolicy::Clamp if card.id < last_id => Ok(card.id + 1..=last_id),
+            CopyPolicy::Clamp => Ok(no_copies()),
+        }
+    }
+}
+
+fn no_copies() -> RangeInclusive<u8> {
+    RangeInclusive::new(1, 0)
+}
+
+/// Checks every line of the input, returning all issues found rather than stopping at the first.
+pub fn validate(input: &str, policy: CopyPolicy) -> Vec<ValidationIssue> {
+    let mut issues = vec![];
+    let mut parsed: Vec<(u8, Vec<u8>, Vec<u8>)> = vec![];
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match Card::parse_line(line) {
+            Ok(card) => parsed.push(card),
+            Err(reason) => issues.push(ValidationIssue::MalformedLine {
+                line_number: i + 1,
+                reason,
+            }),
+        }
+    }
+
+    let mut seen_ids = HashSet::new();
+    let mut expected_id = None;
+    let mut expected_lengths = None;
+    for (id, winning_numbers, player_numbers) in parsed.iter() {
+        issues.extend(duplicate_numbers(*id, NumberList::Winning, winning_numbers));
+        issues.extend(duplicate_numbers(*id, NumberList::Player, player_numbers));
+
+        if !seen_ids.insert(*id) {
+            issues.push(ValidationIssue::RepeatedId { card_id: *id });
+        } else if let Some(expected) = expected_id {
+            if *id != expected {
+                issues.push(ValidationIssue::IdGap {
+                    expected,
+                    found: *id,
+                });
+            }
+        }
+        expected_id = id.checked_add(1);
+
+        let (expected_winning, expected_player) =
+            *expected_lengths.get_or_insert((winning_numbers.len(), player_numbers.len()));
+        if winning_numbers.len() != expected_winning {
+            issues.push(ValidationIssue::MismatchedListLength {
+                card_id: *id,
+                list: NumberList::Winning,
+                expected: expected_winning,
+                found: winning_numbers.len(),
+            });
+        }
+        if player_numbers.len() != expected_player {
+            issues.push(ValidationIssue::MismatchedListLength {
+                card_id: *id,
+                list: NumberList::Player,
+                expected: expected_player,
+                found: player_numbers.len(),
+            });
+        }
+    }
+
+    if let Some(last_id) = seen_ids.iter().max() {
+        for (id, winning_numbers, player_numbers) in parsed.iter() {
+            let card = Card {
+                id: *id,
+                winning_numbers: winning_numbers.iter().copied().collect(),
+                player_numbers: player_numbers.iter().copied().collect(),
+            };
+            if let Err(issue) = policy.copied_ids(&card, *last_id) {
+                issues.push(issue);
+            }
+        }
+    }
+
+    issues
+}
+
+fn duplicate_numbers(card_id: u8, list: NumberList, numbers: &[u8]) -> Vec<ValidationIssue> {
+    let mut seen = HashSet::new();
+    let mut reported = HashSet::new();
+    numbers
+        .iter()
+        .filter(|number| !seen.insert(**number) && reported.insert(**number))
+        .map(|number| ValidationIssue::DuplicateNumber {
+            card_id,
+            list,
+            number: *number,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod a_validator {
+    use rstest::rstest;
+
+    use super::*;
+
+    const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn accepts_the_example() {
+        assert_eq!(
+            Vec::<ValidationIssue>::new(),
+            validate(EXAMPLE, CopyPolicy::Error)
+        );
+    }
+
+    #[test]
+    fn reports_duplicate_numbers_once() {
+        let input = "Card 1: 1 2 2 2 | 3 4 5 3";
+        assert_eq!(
+            vec![
+                ValidationIssue::DuplicateNumber {
+                    card_id: 1,
+                    list: NumberList::Winning,
+                    number: 2
+                },
+                ValidationIssue::DuplicateNumber {
+                    card_id: 1,
+                    list: NumberList::Player,
+                    number: 3
+                },
+            ],
+            validate(input, CopyPolicy::Error)
+        );
+    }
+
+    #[test]
+    fn reports_id_gaps_and_repeats() {
+        let input = "Card 1: 1 | 2\nCard 3: 1 | 2\nCard 3: 1 | 2";
+        assert_eq!(
+            vec![
+                ValidationIssue::IdGap {
+                    expected: 2,
+                    found: 3
+                },
+                ValidationIssue::RepeatedId { card_id: 3 },
+            ],
+            validate(input, CopyPolicy::Error)
+        );
+    }
+
+    #[test]
+    fn reports_mismatched_list_lengths() {
+        let input = "Card 1: 1 2 | 3 4 5\nCard 2: 1 | 3 4 5 6";
+        assert_eq!(
+            vec![
+                ValidationIssue::MismatchedListLength {
+                    card_id: 2,
+                    list: NumberList::Winning,
+                    expected: 2,
+                    found: 1
+                },
+                ValidationIssue::MismatchedListLength {
+                    card_id: 2,
+                    list: NumberList::Player,
+                    expected: 3,
+                    found: 4
+                },
+            ],
+            validate(input, CopyPolicy::Error)
+        );
+    }
+
+    #[test]
+    fn reports_malformed_lines_and_checks_the_rest() {
+        let input = "Card 1: 1 2 | 3 4\nCard\n\nCard 2: 1 2 | 3 x\nCard 3: 1 1 | 3 4";
+        let issues = validate(input, CopyPolicy::Error);
+        let line_numbers: Vec<usize> = issues
+            .iter()
+            .filter_map(|issue| match issue {
+                ValidationIssue::MalformedLine { line_number, .. } => Some(*line_number),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(vec![2, 4], line_numbers);
+        assert!(issues.contains(&ValidationIssue::DuplicateNumber {
+            card_id: 3,
+            list: NumberList::Winning,
+            number: 1
+        }));
+    }
+
+    #[rstest]
+    #[case(ValidationIssue::MalformedLine { line_number: 1, reason: String::new() }, true, true)]
+    #[case(ValidationIssue::RepeatedId { card_id: 1 }, true, true)]
+    #[case(ValidationIssue::IdGap { expected: 2, found: 3 }, true, true)]
+    #[case(
+        ValidationIssue::CopiesPastEnd { card_id: 2, last_copied_id: 4, last_id: 3 },
+        true,
+        false
+    )]
+    #[case(
+        ValidationIssue::DuplicateNumber { card_id: 1, list: NumberList::Player, number: 3 },
+        false,
+        false
+    )]
+    #[case(
+        ValidationIssue::MismatchedListLength {
+            card_id: 2,
+            list: NumberList::Winning,
+            expected: 2,
+            found: 1
+        },
+        false,
+        false
+    )]
+    fn can_tell_which_issues_each_policy_can_not_handle(
+        #[case] issue: ValidationIssue,
+        #[case] fatal_to_error: bool,
+        #[case] fatal_to_clamp: bool,
+    ) {
+        assert_eq!(fatal_to_error, issue.is_fatal(CopyPolicy::Error));
+        assert_eq!(fatal_to_clamp, issue.is_fatal(CopyPolicy::Clamp));
+    }
+
+    #[rstest]
+    #[case(CopyPolicy::Error, 1)]
+    #[case(CopyPolicy::Clamp, 0)]
+    fn reports_copies_past_end_depending_on_policy(
+        #[case] policy: CopyPolicy,
+        #[case] no_of_issues: usize,
+    ) {
+        let input = "Card 1: 1 2 | 5 6\nCard 2: 1 2 | 1 2";
+        assert_eq!(no_of_issues, validate(input, policy).len());
+    }
+
+    #[rstest]
+    #[case(CopyPolicy::Clamp, "Card 2: 1 2 | 1 2", Ok(vec![3]))]
+    #[case(CopyPolicy::Clamp, "Card 3: 1 2 | 1 2", Ok(vec![]))]
+    #[case(CopyPolicy::Error, "Card 1: 1 2 | 1 2", Ok(vec![2, 3]))]
+    #[case(
+        CopyPolicy::Error,
+        "Card 2: 1 2 | 1 2",
+        Err(ValidationIssue::CopiesPastEnd { card_id: 2, last_copied_id: 4, last_id: 3 })
+    )]
+    fn can_get_copied_ids(
+        #[case] policy: CopyPolicy,
+        #[case] line: &str,
+        #[case] expected: Result<Vec<u8>, ValidationIssue>,
+    ) {
+        let card = Card::from_line(line);
+        let copied_ids = policy.copied_ids(&card, 3).map(|ids| ids.collect());
+        assert_eq!(expected, copied_ids);
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::item_map::{ItemMap, ItemMapEntry};
+use crate::route::Route;
+use crate::unsigned::Unsigned;
+
+#[derive(Debug)]
+pub struct Almanac<T = u64> {
+    pub seeds: Vec<T>,
+    /// The category graph, with maps keyed by their source category.
+    maps: HashMap<String, Vec<ItemMap<T>>>,
+}
+
+impl<T: Unsigned> Almanac<T> {
+    /// Builds the category graph, rejecting repeated maps and cycles.
+    pub fn new(seeds: Vec<T>, item_maps: Vec<ItemMap<T>>) -> Result<Self, String> {
+        let mut maps: HashMap<String, Vec<ItemMap<T>>> = HashMap::new();
+        for map in item_maps {
+            let existing = maps.entry(map.source().to_string()).or_default();
+            if existing
+                .iter()
+                .any(|other| other.destination() == map.destination())
+            {
+                return Err(format!("Map '{}' appears more than once", map.id()));
+            }
+            existing.push(map);
+        }
+        let almanac = Almanac { seeds, maps };
+        if let Some(cycle) = almanac.find_cycle() {
+            return Err(format!("Maps form a cycle {}", cycle.join(" -> ")));
+        }
+        Ok(almanac)
+    }
+
+    /// Reads the seed values as pairs of range start and range length.
+    pub fn seed_ranges(&self) -> Result<Vec<Range<T>>, String> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(format!("Odd number of seed values {}", self.seeds.len()));
+        }
+        self.seeds
+            .chunks(2)
+            .map(|pair| match pair[0].checked_add(pair[1]) {
+                Some(end) => Ok(pair[0]..end),
+                None => Err(format!(
+                    "Seed range starting at {} with length {} overflows {}",
+                    pair[0],
+                    pair[1],
+                    T::NAME
+                )),
+            })
+            .collect()
+    }
+
+    pub fn maps(&self) -> impl Iterator<Item = &ItemMap<T>> {
+        self.maps.values().flatten()
+    }
+
+    /// Finds the shortest route of maps from one category to another.
+    pub fn route(&self, source: &str, destination: &str) -> Result<Route<'_, T>, String> {
+        for category in [source, destination] {
+            if !self.categories().contains(category) {
+                return Err(format!("Almanac has no category '{}'", category));
+            }
+        }
+
+        // Breadth first search, remembering the map used to reach each category
+        let mut reached_by: HashMap<&str, Option<&ItemMap<T>>> = HashMap::new();
+        reached_by.insert(source, None);
+        let mut queue = VecDeque::from([source]);
+        while let Some(category) = queue.pop_front() {
+            if category == destination {
+                break;
+            }
+            for map in self.maps.get(category).into_iter().flatten() {
+                if !reached_by.contains_key(map.destination()) {
+                    reached_by.insert(map.destination(), Some(map));
+                    queue.push_back(map.destination());
+                }
+            }
+        }
+
+        if !reached_by.contains_key(destination) {
+            return Err(format!(
+                "No route of maps from '{}' to '{}'",
+                source, destination
+            ));
+        }
+        let mut maps = vec![];
+        let mut category = destination;
+        while let Some(Some(map)) = reached_by.get(category) {
+            maps.push(*map);
+            category = map.source();
+        }
+        maps.reverse();
+        Ok(Route::new(source, destination, maps))
+    }
+
+    /// The route the puzzle follows, from `seed` to `location`.
+    pub fn seed_to_location(&self) -> Result<Route<'_, T>, String> {
+        self.route("seed", "location")
+    }
+
+    fn categories(&self) -> HashSet<&str> {
+        self.maps()
+            .flat_map(|map| [map.source(), map.destination()])
+            .collect()
+    }
+
+    /// Depth first search for a category that can be reached from itself.
+    fn find_cycle(&self) -> Option<Vec<&str>> {
+        fn visit<'a, T: Unsigned>(
+            almanac: &'a Almanac<T>,
+            category: &'a str,
+            path: &mut Vec<&'a str>,
+            finished: &mut HashSet<&'a str>,
+        ) -> Option<Vec<&'a str>> {
+            if let Some(start) = path.iter().position(|visited| *visited == category) {
+                let mut cycle = path[start..].to_vec();
+                cycle.push(category);
+                return Some(cycle);
+            }
+            if finished.contains(category) {
+                return None;
+            }
+            path.push(category);
+            for map in almanac.maps.get(category).into_iter().flatten() {
+                if let Some(cycle) = visit(almanac, map.destination(), path, finished) {
+                    return Some(cycle);
+                }
+            }
+            path.pop();
+            finished.insert(category);
+            None
+        }
+
+        let mut sources: Vec<&str> = self.maps.keys().map(String::as_str).collect();
+        sources.sort();
+        let mut finished = HashSet::new();
+        sources
+            .into_iter()
+            .find_map(|source| visit(self, source, &mut vec![], &mut finished))
+    }
+}
+
+impl<T: Unsigned> FromStr for Almanac<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+
+        let seed_line = lines.next().ok_or("Input had no seed line")?;
+        let seeds = seed_line.strip_prefix("seeds:").ok_or(format!(
+            "Seed line '{}' does not start with 'seeds:'",
+            seed_line
+        ))?;
+        let seeds = seeds
+            .split_whitespace()
+            .map(|seed| {
+                seed.parse::<T>().map_err(|err| {
+                    format!("Could not parse seed '{}' as {}, {}", seed, T::NAME, err)
+                })
+            })
+            .collect::<Result<Vec<T>, String>>()?;
+
+        let mut sections: Vec<(&str, Vec<ItemMapEntry<T>>)> = vec![];
+        for line in lines {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(map_id) = line.strip_suffix(':') {
+                sections.push((map_id, vec![]));
+                continue;
+            }
+            let entry = line.parse::<ItemMapEntry<T>>()?;
+            match sections.last_mut() {
+                Some((_, entries)) => entries.push(entry),
+                None => return Err(format!("Map entry '{}' appears before any map", line)),
+            }
+        }
+        let maps = sections
+            .into_iter()
+            .map(|(map_id, entries)| ItemMap::new(map_id.to_string(), entries))
+            .collect::<Result<Vec<ItemMap<T>>, String>>()?;
+
+        Almanac::new(seeds, maps)
+    }
+}
+
+#[cfg(test)]
+mod an_almanac {
+    use rstest::rstest;
+
+    use super::Almanac;
+    use crate::item_map::ItemMap;
+
+    const TEST_DATA: &str = include_str!("../res/test_data.txt");
+
+    #[test]
+    fn can_be_parsed() {
+        let almanac: Almanac = TEST_DATA.parse().unwrap();
+        assert_eq!(vec![79, 14, 55, 13], almanac.seeds);
+        assert_eq!(7, almanac.maps().count());
+        let seed_to_soil = &almanac.maps["seed"][0];
+        assert_eq!("seed-to-soil map", seed_to_soil.id());
+        assert_eq!(2, seed_to_soil.entries().len());
+        let humidity_to_location = &almanac.maps["humidity"][0];
+        assert_eq!("humidity-to-location map", humidity_to_location.id());
+        assert_eq!(2, humidity_to_location.entries().len());
+    }
+
+    #[test]
+    fn can_route_from_seed_to_location() {
+        let almanac: Almanac = TEST_DATA.parse().unwrap();
+        let route = almanac.seed_to_location().unwrap();
+        let ids: Vec<String> = route.maps().iter().map(|map| map.id()).collect();
+        assert_eq!(
+            vec![
+                "seed-to-soil map",
+                "soil-to-fertilizer map",
+                "fertilizer-to-water map",
+                "water-to-light map",
+                "light-to-temperature map",
+                "temperature-to-humidity map",
+                "humidity-to-location map",
+            ],
+            ids
+        );
+    }
+
+    #[rstest]
+    #[case("soil", "humidity", 5)]
+    #[case("water", "water", 0)]
+    #[case("light", "location", 3)]
+    fn can_route_between_any_connected_categories(
+        #[case] source: &str,
+        #[case] destination: &str,
+        #[case] no_of_maps: usize,
+    ) {
+        let almanac: Almanac = TEST_DATA.parse().unwrap();
+        let route = almanac.route(source, destination).unwrap();
+        assert_eq!(no_of_maps, route.maps().len());
+        assert_eq!(source, route.source());
+        assert_eq!(destination, route.destination());
+    }
+
+    #[test]
+    fn can_take_the_shortest_of_several_routes() {
+        let input = "seeds: 1
+
+seed-to-soil map:
+1 2 3
+
+soil-to-location map:
+1 2 3
+
+seed-to-location map:
+4 5 6";
+        let almanac: Almanac = input.parse().unwrap();
+        let route = almanac.seed_to_location().unwrap();
+        assert_eq!(1, route.maps().len());
+        assert_eq!("seed-to-location map", route.maps()[0].id());
+    }
+
+    #[rstest]
+    #[case("location", "seed", "No route")]
+    #[case("seed", "gold", "no category 'gold'")]
+    #[case("gold", "seed", "no category 'gold'")]
+    fn can_not_route_to_unreachable_categories(
+        #[case] source: &str,
+        #[case] destination: &str,
+        #[case] message: &str,
+    ) {
+        let almanac: Almanac = TEST_DATA.parse().unwrap();
+        let err = almanac.route(source, destination).unwrap_err();
+        assert!(err.contains(message), "{}", err);
+    }
+
+    #[test]
+    fn rejects_cycles() {
+        let maps = ["seed-to-soil map", "soil-to-water map", "water-to-soil map"]
+            .into_iter()
+            .map(|id| ItemMap::new(id.to_string(), vec![]).unwrap())
+            .collect();
+        let err = Almanac::<u64>::new(vec![], maps).unwrap_err();
+        assert_eq!("Maps form a cycle soil -> water -> soil", err);
+    }
+
+    #[test]
+    fn rejects_repeated_maps() {
+        let input = "seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nseed-to-soil map:\n4 5 6";
+        let err = input.parse::<Almanac>().unwrap_err();
+        assert!(err.contains("seed-to-soil map"), "{}", err);
+    }
+
+    #[test]
+    fn can_get_seed_ranges() {
+        let almanac: Almanac = TEST_DATA.parse().unwrap();
+        assert_eq!(Ok(vec![79..93, 55..68]), almanac.seed_ranges());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("seed: 1 2")]
+    #[case("seeds: 1 x")]
+    #[case("seeds: 1\n\n1 2 3")]
+    #[case("seeds: 1\n\nseed-soil map:\n1 2 3")]
+    #[case("seeds: 1\n\nseed-to-soil map:\n1 2")]
+    #[case("seeds: 1\n\nseed-to-soil map:\n1 2 3\n5 3 1")]
+    #[case("seeds: 1\n\nseed-to-seed map:\n1 2 3")]
+    fn rejects_invalid_input(#[case] input: &str) {
+        assert!(input.parse::<Almanac>().is_err());
+    }
+}
@@ -1,6 +1,6 @@
-use std::{collections::VecDeque, env, fs};
+use std::{env, fs};
 
-use day_05::item_map::{ItemMap, ItemMapEntry};
+use day_05::almanac::Almanac;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -9,56 +9,24 @@ fn main() {
     println!("Reading from file '{}'", file_path);
 
     let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let mut lines = contents.lines();
-
-    // Get seeds
-    let seed_line = lines.next().expect("Input had no seed line");
-    let seed_line = &seed_line[7..];
-    let seeds: Vec<u64> = seed_line
-        .split_whitespace()
-        .map(|seed| seed.parse::<u64>().expect("Could not parse seed as u64"))
-        .collect();
-    _ = lines.next();
-
-    let mut maps = VecDeque::<ItemMap>::new();
-    let line = lines.next().unwrap();
-    let mut map_id = &line[..line.len() - 1];
-    let mut current_map = ItemMap {
-        id: map_id.to_string(),
-        entries: vec![],
+    let almanac: Almanac = match contents.parse() {
+        Ok(almanac) => almanac,
+        Err(err) => panic!("{}", err),
+    };
+    let seed_to_location = match almanac.seed_to_location() {
+        Ok(route) => route,
+        Err(err) => panic!("{}", err),
     };
-    while let Some(line) = lines.next() {
-        if line.contains("map") {
-            maps.push_back(current_map);
-            map_id = &line[..line.len() - 1];
-            current_map = ItemMap {
-                id: map_id.to_string(),
-                entries: vec![],
-            };
-        } else if line.len() == 0 {
-            continue;
-        } else {
-            current_map.entries.push(ItemMapEntry::from_line(line));
-        }
-    }
-    maps.push_back(current_map);
 
-    let lowest_location_number = seeds
+    let lowest_location_number = almanac
+        .seeds
         .iter()
-        .map(|seed| map_continuous(&maps, seed))
+        .map(|seed| seed_to_location.map_continuous(seed))
         .min()
         .expect("Map was empty");
     print!("Result = {}", lowest_location_number);
 }
 
-fn map_continuous(maps: &VecDeque<ItemMap>, seed: &u64) -> u64 {
-    let mut result = *seed;
-    for map in maps.iter() {
-        result = ItemMap::get(&map.entries, &result);
-    }
-    result
-}
-
 #[cfg(test)]
 mod tests {
     use day_05::item_map::{ItemMap, ItemMapEntry};
@@ -81,7 +49,8 @@ mod tests {
             source_range_start: 50,
             range_length: 48,
         });
+        let map = ItemMap::new("seed-to-soil map".to_string(), mappings).unwrap();
 
-        assert_eq!(soil, ItemMap::get(&mappings, &seed));
+        assert_eq!(soil, map.get(&seed));
     }
 }
@@ -0,0 +1,93 @@
+use std::ops::RangeInclusive;
+use std::{env, fs};
+
+use day_05::almanac::Almanac;
+use day_05::trace::{format_range_table, format_table, trace, trace_range};
+
+/// Prints each stage a seed, or range of seeds, passes through on its way to a location.
+///
+/// Usage: `trace <file> <seed | start..end | start..=last> [--json]`
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let file_path = &args[1];
+    let seeds = args.get(2).expect("Expected a seed or seed range to trace");
+    let json = args.iter().skip(3).any(|arg| arg == "--json");
+
+    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
+    let almanac: Almanac = match contents.parse() {
+        Ok(almanac) => almanac,
+        Err(err) => panic!("{}", err),
+    };
+    let seed_to_location = match almanac.seed_to_location() {
+        Ok(route) => route,
+        Err(err) => panic!("{}", err),
+    };
+
+    let output = match parse_seeds(seeds) {
+        Ok(Seeds::Single(seed)) => {
+            let steps = trace(&seed_to_location, seed);
+            match json {
+                true => serde_json::to_string_pretty(&steps).expect("Failed to serialize trace"),
+                false => format_table(&steps),
+            }
+        }
+        Ok(Seeds::Range(seeds)) => {
+            let steps = trace_range(&seed_to_location, seeds);
+            match json {
+                true => serde_json::to_string_pretty(&steps).expect("Failed to serialize trace"),
+                false => format_range_table(&steps),
+            }
+        }
+        Err(err) => panic!("{}", err),
+    };
+    println!("{}", output.trim_end());
+}
+
+#[derive(Debug, PartialEq)]
+enum Seeds {
+    Single(u64),
+    Range(RangeInclusive<u64>),
+}
+
+fn parse_seeds(value: &str) -> Result<Seeds, String> {
+    let parse = |value: &str| {
+        value
+            .parse::<u64>()
+            .map_err(|err| format!("Could not parse seed '{}' as u64, {}", value, err))
+    };
+    if let Some((start, last)) = value.split_once("..=") {
+        return Ok(Seeds::Range(parse(start)?..=parse(last)?));
+    }
+    if let Some((start, end)) = value.split_once("..") {
+        let end = parse(end)?;
+        let last = end
+            .checked_sub(1)
+            .ok_or(format!("Seed range '{}' is empty", value))?;
+        return Ok(Seeds::Range(parse(start)?..=last));
+    }
+    Ok(Seeds::Single(parse(value)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{parse_seeds, Seeds};
+
+    #[rstest]
+    #[case("79", Ok(Seeds::Single(79)))]
+    #[case("79..93", Ok(Seeds::Range(79..=92)))]
+    #[case("79..=92", Ok(Seeds::Range(79..=92)))]
+    fn can_parse_seeds(#[case] value: &str, #[case] expected: Result<Seeds, String>) {
+        assert_eq!(expected, parse_seeds(value));
+    }
+
+    #[rstest]
+    #[case("seed")]
+    #[case("79..")]
+    #[case("0..0")]
+    fn rejects_invalid_seeds(#[case] value: &str) {
+        assert!(parse_seeds(value).is_err());
+    }
+}
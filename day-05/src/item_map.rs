@@ -1,51 +1,609 @@
-#[derive(Debug)]
-pub struct ItemMapEntry {
-    pub destination_range_start: u64,
-    pub source_range_start: u64,
-    pub range_length: u64,
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::unsigned::Unsigned;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ItemMapEntry<T = u64> {
+    pub destination_range_start: T,
+    pub source_range_start: T,
+    pub range_length: T,
 }
 
-impl ItemMapEntry {
+impl<T: Unsigned> ItemMapEntry<T> {
     pub fn from_line(line: &str) -> Self {
+        match line.parse() {
+            Ok(entry) => entry,
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+impl<T: Unsigned> fmt::Display for ItemMapEntry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.destination_range_start, self.source_range_start, self.range_length
+        )
+    }
+}
+
+impl<T: Unsigned> FromStr for ItemMapEntry<T> {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
         let mut parts = line.split_whitespace();
-        let destination_range_start = parts
-            .next()
-            .expect("No destination range start provided")
-            .parse::<u64>()
-            .expect("Could not parse destination range start as u64");
-        let source_range_start = parts
-            .next()
-            .expect("No source range start provided")
-            .parse::<u64>()
-            .expect("Could not source range start as u64");
-        let range_length = parts
-            .next()
-            .expect("No range length provided")
-            .parse::<u64>()
-            .expect("Could not parse range length as u64");
-        Self {
+        let mut next_value = |name: &str| -> Result<T, String> {
+            let part = parts
+                .next()
+                .ok_or(format!("No {} provided in line '{}'", name, line))?;
+            part.parse::<T>().map_err(|err| {
+                format!(
+                    "Could not parse {} '{}' as {}, {}",
+                    name,
+                    part,
+                    T::NAME,
+                    err
+                )
+            })
+        };
+        let destination_range_start = next_value("destination range start")?;
+        let source_range_start = next_value("source range start")?;
+        let range_length = next_value("range length")?;
+        if parts.next().is_some() {
+            return Err(format!(
+                "Unexpected values after range length in '{}'",
+                line
+            ));
+        }
+        Ok(Self {
             destination_range_start,
             source_range_start,
             range_length,
-        }
+        })
     }
 }
 
+impl<T: Unsigned> ItemMapEntry<T> {
+    /// Maps an item within the entry's source range.
+    pub fn get(&self, item: &T) -> T {
+        self.destination_range_start + (*item - self.source_range_start)
+    }
+
+    /// The last source item covered, or `None` for an empty entry or one that runs past `T::MAX`.
+    fn source_last(&self) -> Option<T> {
+        self.source_range_start
+            .checked_add(self.range_length.checked_sub(T::ONE)?)
+    }
+
+    fn destination_last(&self) -> Option<T> {
+        self.destination_range_start
+            .checked_add(self.range_length.checked_sub(T::ONE)?)
+    }
+}
+
+/// A map whose entries are sorted by source range start and never overlap.
 #[derive(Debug)]
-pub struct ItemMap {
-    pub id: String,
-    pub entries: Vec<ItemMapEntry>,
+pub struct ItemMap<T = u64> {
+    source: String,
+    destination: String,
+    entries: Vec<ItemMapEntry<T>>,
 }
 
-impl ItemMap {
-    pub fn get(mappings: &Vec<ItemMapEntry>, item: &u64) -> u64 {
-        for map in mappings.iter() {
-            if map.source_range_start <= *item && *item < map.source_range_start + map.range_length
+impl<T: Unsigned> ItemMap<T> {
+    /// Sorts the entries, dropping empty ones, and rejects entries that overlap or run past
+    /// the largest `T`.
+    pub fn new(id: String, entries: Vec<ItemMapEntry<T>>) -> Result<Self, String> {
+        let (source, destination) = Self::categories(&id)?;
+        let mut entries: Vec<ItemMapEntry<T>> = entries
+            .into_iter()
+            .filter(|entry| entry.range_length > T::ZERO)
+            .collect();
+        if let Some(entry) = entries
+            .iter()
+            .find(|entry| entry.source_last().is_none() || entry.destination_last().is_none())
+        {
+            return Err(format!(
+                "Entry '{}' in map '{}' runs past the largest {}",
+                entry,
+                id,
+                T::NAME
+            ));
+        }
+        entries.sort_by_key(|entry| entry.source_range_start);
+        if let Some(pair) = entries
+            .windows(2)
+            .find(|pair| pair[0].source_last() >= Some(pair[1].source_range_start))
+        {
+            return Err(format!(
+                "Entries '{}' and '{}' in map '{}' overlap",
+                pair[0], pair[1], id
+            ));
+        }
+        Ok(ItemMap {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            entries,
+        })
+    }
+
+    /// A map from a category to itself that passes every item through unchanged.
+    pub fn identity(category: &str) -> Self {
+        ItemMap {
+            source: category.to_string(),
+            destination: category.to_string(),
+            entries: vec![],
+        }
+    }
+
+    pub fn id(&self) -> String {
+        format!("{}-to-{} map", self.source, self.destination)
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn destination(&self) -> &str {
+        &self.destination
+    }
+
+    pub fn entries(&self) -> &[ItemMapEntry<T>] {
+        &self.entries
+    }
+
+    pub fn get(&self, item: &T) -> T {
+        match self.find_entry(item) {
+            Some(entry) => entry.get(item),
+            None => *item,
+        }
+    }
+
+    /// The source and destination categories named in an id like `seed-to-soil map`.
+    fn categories(id: &str) -> Result<(&str, &str), String> {
+        let categories = id
+            .strip_suffix(" map")
+            .ok_or(format!("Map id '{}' does not end with ' map'", id))?;
+        match categories.split_once("-to-") {
+            Some((source, destination)) if !source.is_empty() && !destination.is_empty() => {
+                Ok((source, destination))
+            }
+            _ => Err(format!("Map id '{}' is not of the form 'a-to-b map'", id)),
+        }
+    }
+
+    /// Finds the entry containing `item` with a binary search.
+    pub fn find_entry(&self, item: &T) -> Option<&ItemMapEntry<T>> {
+        let index = self
+            .entries
+            .partition_point(|entry| entry.source_range_start <= *item);
+        let entry = self.entries.get(index.checked_sub(1)?)?;
+        match *item - entry.source_range_start < entry.range_length {
+            true => Some(entry),
+            false => None,
+        }
+    }
+
+    /// Builds a single map equivalent to applying `self` and then `next`. Ranges both maps pass
+    /// through unchanged get no entry.
+    pub fn compose(&self, next: &ItemMap<T>) -> Result<ItemMap<T>, String> {
+        if self.destination != next.source {
+            return Err(format!(
+                "Can not compose '{}' with '{}', '{}' is not '{}'",
+                self.id(),
+                next.id(),
+                self.destination,
+                next.source
+            ));
+        }
+
+        let next_pieces = next.pieces();
+        let mut composed: Vec<Piece<T>> = vec![];
+        for piece in self.pieces() {
+            let image_first = piece.destination_first;
+            let image_last = piece.destination_first + (piece.last - piece.first);
+            let start = next_pieces.partition_point(|next_piece| next_piece.last < image_first);
+            for next_piece in next_pieces[start..]
+                .iter()
+                .take_while(|next_piece| next_piece.first <= image_last)
             {
-                let offset = *item - map.source_range_start;
-                return map.destination_range_start + offset;
+                let first = image_first.max(next_piece.first);
+                let last = image_last.min(next_piece.last);
+                let new_piece = Piece {
+                    first: piece.first + (first - image_first),
+                    last: piece.first + (last - image_first),
+                    destination_first: next_piece.destination_first + (first - next_piece.first),
+                };
+                match composed.last_mut() {
+                    Some(previous) if previous.continues_into(&new_piece) => {
+                        previous.last = new_piece.last
+                    }
+                    _ => composed.push(new_piece),
+                }
+            }
+        }
+
+        Ok(ItemMap {
+            source: self.source.clone(),
+            destination: next.destination.clone(),
+            entries: composed
+                .into_iter()
+                .filter(|piece| piece.first != piece.destination_first)
+                .map(|piece| ItemMapEntry {
+                    destination_range_start: piece.destination_first,
+                    source_range_start: piece.first,
+                    range_length: piece.length(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Splits a range of items into the parts covered by each entry, and the parts between
+    /// entries which pass through unchanged.
+    pub fn segments(
+        &self,
+        items: RangeInclusive<T>,
+    ) -> Vec<(RangeInclusive<T>, Option<&ItemMapEntry<T>>)> {
+        let mut segments = vec![];
+        let start = self
+            .entries
+            .partition_point(|entry| entry.source_last() < Some(*items.start()));
+        let mut next_first = Some(*items.start()).filter(|_| !items.is_empty());
+        for entry in self.entries[start..].iter() {
+            let Some(first) = next_first else { break };
+            if *items.end() < entry.source_range_start {
+                break;
+            }
+            if first < entry.source_range_start {
+                segments.push((first..=entry.source_range_start - T::ONE, None));
+            }
+            let last = entry
+                .source_last()
+                .expect("Entries are checked on construction")
+                .min(*items.end());
+            segments.push((first.max(entry.source_range_start)..=last, Some(entry)));
+            next_first = last.checked_add(T::ONE).filter(|next| next <= items.end());
+        }
+        if let Some(first) = next_first {
+            segments.push((first..=*items.end(), None));
+        }
+        segments
+    }
+
+    /// Maps a range of items forwards, returning the sorted, merged ranges it lands in.
+    pub fn map_range(&self, items: RangeInclusive<T>) -> Vec<RangeInclusive<T>> {
+        let pieces = self.pieces();
+        let start = pieces.partition_point(|piece| piece.last < *items.start());
+        let ranges = pieces[start..]
+            .iter()
+            .take_while(|piece| piece.first <= *items.end())
+            .map(|piece| {
+                let first = piece.first.max(*items.start());
+                let last = piece.last.min(*items.end());
+                piece.destination_of(first)..=piece.destination_of(last)
+            })
+            .collect();
+        merge_ranges(ranges)
+    }
+
+    /// Every source item that maps to `destination`, in ascending order. There may be several,
+    /// or none, as entries can map onto each other's ranges.
+    pub fn inverse_get(&self, destination: &T) -> Vec<T> {
+        self.pieces()
+            .iter()
+            .filter(|piece| piece.destination_range().contains(destination))
+            .map(|piece| piece.first + (*destination - piece.destination_first))
+            .collect()
+    }
+
+    /// Every source range that maps into `destinations`, sorted and merged.
+    pub fn inverse_range(&self, destinations: RangeInclusive<T>) -> Vec<RangeInclusive<T>> {
+        let ranges = self
+            .pieces()
+            .iter()
+            .filter_map(|piece| {
+                let image = piece.destination_range();
+                let first = *image.start().max(destinations.start());
+                let last = *image.end().min(destinations.end());
+                match first <= last {
+                    true => Some(
+                        piece.first + (first - piece.destination_first)
+                            ..=piece.first + (last - piece.destination_first),
+                    ),
+                    false => None,
+                }
+            })
+            .collect();
+        merge_ranges(ranges)
+    }
+
+    /// Splits the whole domain of `T` into sorted pieces, filling the gaps between entries with
+    /// identity pieces.
+    pub(crate) fn pieces(&self) -> Vec<Piece<T>> {
+        let mut pieces = vec![];
+        let mut next_first = Some(T::ZERO);
+        for entry in self.entries.iter() {
+            let Some(cursor) = next_first else { break };
+            let last = entry
+                .source_last()
+                .expect("Entries are checked on construction");
+            if cursor < entry.source_range_start {
+                pieces.push(Piece::identity(cursor, entry.source_range_start - T::ONE));
             }
+            pieces.push(Piece {
+                first: entry.source_range_start,
+                last,
+                destination_first: entry.destination_range_start,
+            });
+            next_first = last.checked_add(T::ONE);
         }
-        item.clone()
+        if let Some(cursor) = next_first {
+            pieces.push(Piece::identity(cursor, T::MAX));
+        }
+        pieces
+    }
+}
+
+impl<T: Unsigned> fmt::Display for ItemMap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}:", self.id())?;
+        for entry in self.entries.iter() {
+            writeln!(f, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+/// An inclusive source range mapped linearly onto a destination range.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Piece<T> {
+    pub(crate) first: T,
+    pub(crate) last: T,
+    pub(crate) destination_first: T,
+}
+
+impl<T: Unsigned> Piece<T> {
+    fn identity(first: T, last: T) -> Self {
+        Piece {
+            first,
+            last,
+            destination_first: first,
+        }
+    }
+
+    pub(crate) fn destination_of(&self, item: T) -> T {
+        self.destination_first + (item - self.first)
+    }
+
+    pub(crate) fn destination_range(&self) -> RangeInclusive<T> {
+        self.destination_first..=self.destination_of(self.last)
+    }
+
+    /// The number of items in the piece, which only fits in `T` if the piece does not cover
+    /// the whole domain.
+    fn length(&self) -> T {
+        (self.last - self.first)
+            .checked_add(T::ONE)
+            .expect("Piece covers every value of T")
+    }
+
+    fn continues_into(&self, next: &Piece<T>) -> bool {
+        self.last.checked_add(T::ONE) == Some(next.first)
+            && self.destination_of(self.last).checked_add(T::ONE) == Some(next.destination_first)
+    }
+}
+
+/// Sorts ranges and merges any that overlap or touch.
+pub(crate) fn merge_ranges<T: Unsigned>(
+    mut ranges: Vec<RangeInclusive<T>>,
+) -> Vec<RangeInclusive<T>> {
+    ranges.sort_by_key(|range| *range.start());
+    let mut merged: Vec<RangeInclusive<T>> = vec![];
+    for range in ranges {
+        match merged.last_mut() {
+            Some(previous)
+                if previous.end().checked_add(T::ONE).unwrap_or(T::MAX) >= *range.start() =>
+            {
+                *previous = *previous.start()..=*previous.end().max(range.end())
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod an_item_map {
+    use rstest::rstest;
+    use std::ops::RangeInclusive;
+
+    use super::{ItemMap, ItemMapEntry};
+
+    fn seed_to_soil() -> ItemMap {
+        ItemMap::new(
+            "seed-to-soil map".to_string(),
+            vec![
+                ItemMapEntry::from_line("50 98 2"),
+                ItemMapEntry::from_line("52 50 48"),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn soil_to_fertilizer() -> ItemMap {
+        ItemMap::new(
+            "soil-to-fertilizer map".to_string(),
+            vec![
+                ItemMapEntry::from_line("0 15 37"),
+                ItemMapEntry::from_line("37 52 2"),
+                ItemMapEntry::from_line("39 0 15"),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    #[case("seed-to-soil", "'seed-to-soil'")]
+    #[case("seed-soil map", "seed-soil map")]
+    #[case("-to-soil map", "-to-soil map")]
+    fn rejects_invalid_ids(#[case] id: &str, #[case] named: &str) {
+        let err = ItemMap::<u64>::new(id.to_string(), vec![]).unwrap_err();
+        assert!(err.contains(named), "{}", err);
+    }
+
+    #[test]
+    fn can_get_its_categories() {
+        let map = seed_to_soil();
+        assert_eq!("seed", map.source());
+        assert_eq!("soil", map.destination());
+        assert_eq!("seed-to-soil map", map.id());
+    }
+
+    #[test]
+    fn sorts_its_entries() {
+        let sources: Vec<u64> = soil_to_fertilizer()
+            .entries()
+            .iter()
+            .map(|entry| entry.source_range_start)
+            .collect();
+        assert_eq!(vec![0, 15, 52], sources);
+    }
+
+    #[rstest]
+    #[case(vec!["50 98 2", "52 50 49"])]
+    #[case(vec!["0 10 5", "20 12 1"])]
+    #[case(vec!["0 10 5", "20 10 5"])]
+    fn rejects_overlapping_entries(#[case] lines: Vec<&str>) {
+        let entries = lines.into_iter().map(ItemMapEntry::from_line).collect();
+        let err = ItemMap::<u64>::new("seed-to-soil map".to_string(), entries).unwrap_err();
+        assert!(err.contains("seed-to-soil map"), "{}", err);
+        assert!(err.contains("overlap"), "{}", err);
+    }
+
+    #[rstest]
+    #[case("0 18446744073709551615 2")]
+    #[case("18446744073709551615 0 2")]
+    fn rejects_entries_past_u64_max(#[case] line: &str) {
+        let entries = vec![ItemMapEntry::<u64>::from_line(line)];
+        let err = ItemMap::new("seed-to-soil map".to_string(), entries).unwrap_err();
+        assert!(err.contains("seed-to-soil map"), "{}", err);
+    }
+
+    #[rstest]
+    #[case(u64::MAX - 2, u64::MAX - 2)]
+    #[case(u64::MAX - 1, 0)]
+    #[case(u64::MAX, 1)]
+    fn can_map_items_near_u64_max(#[case] item: u64, #[case] expected: u64) {
+        let entries = vec![ItemMapEntry::from_line("0 18446744073709551614 2")];
+        let map = ItemMap::new("seed-to-soil map".to_string(), entries).unwrap();
+        assert_eq!(expected, map.get(&item));
+        assert!(map.inverse_get(&expected).contains(&item));
+    }
+
+    #[rstest]
+    #[case(79, 81)]
+    #[case(14, 14)]
+    #[case(55, 57)]
+    #[case(13, 13)]
+    #[case(98, 50)]
+    #[case(99, 51)]
+    #[case(100, 100)]
+    fn can_get_items(#[case] seed: u64, #[case] soil: u64) {
+        assert_eq!(soil, seed_to_soil().get(&seed));
+    }
+
+    #[test]
+    fn can_be_composed() {
+        let first = seed_to_soil();
+        let second = soil_to_fertilizer();
+        let composed = first.compose(&second).unwrap();
+
+        assert_eq!("seed-to-fertilizer map", composed.id());
+        for seed in 0..200 {
+            let expected = second.get(&first.get(&seed));
+            assert_eq!(expected, composed.get(&seed), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn can_not_be_composed_with_unrelated_map() {
+        assert!(soil_to_fertilizer().compose(&seed_to_soil()).is_err());
+    }
+
+    #[test]
+    fn can_be_printed_in_almanac_format() {
+        let composed = seed_to_soil().compose(&soil_to_fertilizer()).unwrap();
+        let expected = "seed-to-fertilizer map:
+39 0 15
+0 15 35
+37 50 2
+54 52 46
+35 98 2
+";
+        assert_eq!(expected, composed.to_string());
+    }
+
+    #[rstest]
+    #[case(0, vec![0..=3])]
+    #[case(50, vec![52..=55])]
+    #[case(96, vec![50..=51, 98..=99])]
+    #[case(99, vec![51..=51, 100..=102])]
+    fn can_map_ranges(#[case] start: u64, #[case] expected: Vec<RangeInclusive<u64>>) {
+        assert_eq!(expected, seed_to_soil().map_range(start..=start + 3));
+    }
+
+    #[rstest]
+    #[case(0..=3, vec![(0..=3, None)])]
+    #[case(48..=51, vec![(48..=49, None), (50..=51, Some(50))])]
+    #[case(96..=101, vec![(96..=97, Some(50)), (98..=99, Some(98)), (100..=101, None)])]
+    #[case(99..=99, vec![(99..=99, Some(98))])]
+    #[case(u64::MAX..=u64::MAX, vec![(u64::MAX..=u64::MAX, None)])]
+    fn can_split_ranges_into_segments(
+        #[case] items: RangeInclusive<u64>,
+        #[case] expected: Vec<(RangeInclusive<u64>, Option<u64>)>,
+    ) {
+        let map = seed_to_soil();
+        let segments: Vec<(RangeInclusive<u64>, Option<u64>)> = map
+            .segments(items)
+            .into_iter()
+            .map(|(range, entry)| (range, entry.map(|entry| entry.source_range_start)))
+            .collect();
+        assert_eq!(expected, segments);
+    }
+
+    #[rstest]
+    #[case(10, vec![10])]
+    #[case(50, vec![98])]
+    #[case(53, vec![51])]
+    #[case(99, vec![97])]
+    fn can_inverse_items(#[case] soil: u64, #[case] seeds: Vec<u64>) {
+        assert_eq!(seeds, seed_to_soil().inverse_get(&soil));
+    }
+
+    #[rstest]
+    #[case(0..=10, vec![0..=10])]
+    #[case(48..=53, vec![48..=51, 98..=99])]
+    #[case(98..=100, vec![96..=97, 100..=100])]
+    fn can_inverse_ranges(
+        #[case] soil: RangeInclusive<u64>,
+        #[case] seeds: Vec<RangeInclusive<u64>>,
+    ) {
+        assert_eq!(seeds, seed_to_soil().inverse_range(soil));
+    }
+
+    #[rstest]
+    #[case(49, None)]
+    #[case(50, Some(50))]
+    #[case(97, Some(50))]
+    #[case(98, Some(98))]
+    #[case(100, None)]
+    fn can_find_entries(#[case] item: u64, #[case] source_range_start: Option<u64>) {
+        let map = seed_to_soil();
+        let entry = map.find_entry(&item).map(|entry| entry.source_range_start);
+        assert_eq!(source_range_start, entry);
     }
 }
@@ -1 +1,5 @@
+pub mod almanac;
 pub mod item_map;
+pub mod route;
+pub mod trace;
+pub mod unsigned;
@@ -0,0 +1,247 @@
+use std::ops::RangeInclusive;
+
+use crate::item_map::{merge_ranges, ItemMap};
+use crate::unsigned::Unsigned;
+
+/// A path of maps through the category graph, each map's destination being the next map's source.
+#[derive(Debug, Clone)]
+pub struct Route<'a, T = u64> {
+    source: String,
+    destination: String,
+    maps: Vec<&'a ItemMap<T>>,
+}
+
+impl<'a, T: Unsigned> Route<'a, T> {
+    pub(crate) fn new(source: &str, destination: &str, maps: Vec<&'a ItemMap<T>>) -> Self {
+        Route {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            maps,
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn destination(&self) -> &str {
+        &self.destination
+    }
+
+    pub fn maps(&self) -> &[&'a ItemMap<T>] {
+        &self.maps
+    }
+
+    /// Maps an item through every map in turn.
+    pub fn map_continuous(&self, item: &T) -> T {
+        let mut result = *item;
+        for map in self.maps.iter() {
+            result = map.get(&result);
+        }
+        result
+    }
+
+    /// Maps a range of items forwards through every map, returning the destination ranges.
+    pub fn map_range(&self, items: RangeInclusive<T>) -> Vec<RangeInclusive<T>> {
+        self.maps.iter().fold(vec![items], |ranges, map| {
+            merge_ranges(
+                ranges
+                    .into_iter()
+                    .flat_map(|range| map.map_range(range))
+                    .collect(),
+            )
+        })
+    }
+
+    /// Every source item that ends up at `destination`, in ascending order.
+    pub fn inverse_get(&self, destination: &T) -> Vec<T> {
+        let mut items = self
+            .maps
+            .iter()
+            .rev()
+            .fold(vec![*destination], |items, map| {
+                items
+                    .iter()
+                    .flat_map(|item| map.inverse_get(item))
+                    .collect()
+            });
+        items.sort();
+        items.dedup();
+        items
+    }
+
+    /// Every range of source items that ends up within `destinations`, sorted and merged.
+    pub fn inverse_range(&self, destinations: RangeInclusive<T>) -> Vec<RangeInclusive<T>> {
+        self.maps
+            .iter()
+            .rev()
+            .fold(vec![destinations], |ranges, map| {
+                merge_ranges(
+                    ranges
+                        .into_iter()
+                        .flat_map(|range| map.inverse_range(range))
+                        .collect(),
+                )
+            })
+    }
+
+    /// Finds the lowest destination reached by any of `items`, searching the composed map's
+    /// ranges from the lowest destinations upwards and stopping once no lower one is possible.
+    pub fn lowest_destination(&self, items: &[RangeInclusive<T>]) -> Option<T> {
+        let mut pieces = self.composed().pieces();
+        pieces.sort_by_key(|piece| piece.destination_first);
+
+        let mut lowest: Option<T> = None;
+        for piece in pieces {
+            if lowest.is_some_and(|lowest| lowest <= piece.destination_first) {
+                break;
+            }
+            let reached = items
+                .iter()
+                .filter(|range| *range.start() <= piece.last && piece.first <= *range.end())
+                .map(|range| piece.destination_of(piece.first.max(*range.start())))
+                .min();
+            lowest = match (lowest, reached) {
+                (Some(lowest), Some(reached)) => Some(lowest.min(reached)),
+                (lowest, reached) => lowest.or(reached),
+            };
+        }
+        lowest
+    }
+
+    /// Folds every map into a single map from the route's source to its destination.
+    pub fn composed(&self) -> ItemMap<T> {
+        let identity = ItemMap::identity(&self.source);
+        self.maps.iter().fold(identity, |composed, map| {
+            composed
+                .compose(map)
+                .expect("Maps along a route always connect")
+        })
+    }
+}
+
+#[cfg(test)]
+mod a_route {
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+    use rstest::rstest;
+    use std::ops::RangeInclusive;
+
+    use crate::almanac::Almanac;
+    use crate::item_map::{ItemMap, ItemMapEntry};
+
+    const TEST_DATA: &str = include_str!("../res/test_data.txt");
+
+    #[rstest]
+    #[case(79, 82)]
+    #[case(14, 43)]
+    #[case(55, 86)]
+    #[case(13, 35)]
+    fn can_map_seeds_to_locations(#[case] seed: u64, #[case] location: u64) {
+        let almanac: Almanac = TEST_DATA.parse().unwrap();
+        let route = almanac.seed_to_location().unwrap();
+        assert_eq!(location, route.map_continuous(&seed));
+    }
+
+    #[test]
+    fn can_be_composed_into_a_single_map() {
+        let almanac: Almanac = TEST_DATA.parse().unwrap();
+        let route = almanac.seed_to_location().unwrap();
+        let composed = route.composed();
+        assert_eq!("seed-to-location map", composed.id());
+        for seed in 0..150 {
+            assert_eq!(route.map_continuous(&seed), composed.get(&seed));
+        }
+    }
+
+    #[test]
+    fn can_find_seeds_for_a_location() {
+        let almanac: Almanac = TEST_DATA.parse().unwrap();
+        let route = almanac.seed_to_location().unwrap();
+        assert_eq!(vec![82], route.inverse_get(&46));
+        assert!(route
+            .inverse_range(35..=35)
+            .iter()
+            .any(|range| range.contains(&13)));
+    }
+
+    #[test]
+    fn can_find_the_lowest_location_of_seed_ranges() {
+        let almanac: Almanac = TEST_DATA.parse().unwrap();
+        let route = almanac.seed_to_location().unwrap();
+        assert_eq!(Some(46), route.lowest_destination(&[79..=92, 55..=67]));
+        assert_eq!(None, route.lowest_destination(&[]));
+    }
+
+    fn random_almanac(rng: &mut StdRng) -> (Almanac, String) {
+        let no_of_maps = rng.gen_range(1..=5);
+        let maps = (0..no_of_maps)
+            .map(|i| {
+                let mut entries = vec![];
+                let mut next_start = rng.gen_range(0..20);
+                while next_start < 500 {
+                    let range_length = rng.gen_range(1..60);
+                    entries.push(ItemMapEntry {
+                        destination_range_start: rng.gen_range(0..500),
+                        source_range_start: next_start,
+                        range_length,
+                    });
+                    next_start += range_length + rng.gen_range(0..30);
+                }
+                entries.shuffle(rng);
+                ItemMap::new(format!("c{}-to-c{} map", i, i + 1), entries).unwrap()
+            })
+            .collect();
+        (
+            Almanac::new(vec![], maps).unwrap(),
+            format!("c{}", no_of_maps),
+        )
+    }
+
+    #[test]
+    fn round_trips_seeds_through_random_almanacs() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..100 {
+            let (almanac, last_category) = random_almanac(&mut rng);
+            let route = almanac.route("c0", &last_category).unwrap();
+            for _ in 0..20 {
+                let seed = rng.gen_range(0..600);
+                let location = route.map_continuous(&seed);
+                let seeds = route.inverse_get(&location);
+                assert!(seeds.contains(&seed), "{} not in {:?}", seed, seeds);
+                for seed in seeds {
+                    assert_eq!(location, route.map_continuous(&seed));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips_seed_ranges_through_random_almanacs() {
+        let mut rng = StdRng::seed_from_u64(6);
+        for _ in 0..100 {
+            let (almanac, last_category) = random_almanac(&mut rng);
+            let route = almanac.route("c0", &last_category).unwrap();
+            let start = rng.gen_range(0..600);
+            let seeds = start..=start + rng.gen_range(0..100);
+
+            let locations = route.map_range(seeds.clone());
+            for seed in seeds.clone() {
+                let location = route.map_continuous(&seed);
+                assert!(locations.iter().any(|range| range.contains(&location)));
+            }
+
+            let inverse: Vec<RangeInclusive<u64>> = locations
+                .into_iter()
+                .flat_map(|range| route.inverse_range(range))
+                .collect();
+            for seed in seeds.clone() {
+                assert!(inverse.iter().any(|range| range.contains(&seed)));
+            }
+
+            let lowest = seeds.clone().map(|seed| route.map_continuous(&seed)).min();
+            assert_eq!(lowest, route.lowest_destination(&[seeds]));
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+use serde::Serialize;
+
+use crate::item_map::ItemMapEntry;
+use crate::route::Route;
+use crate::unsigned::Unsigned;
+
+/// One map applied to a single item. `entry` is `None` when the item passes through unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceStep<T = u64> {
+    pub source: String,
+    pub destination: String,
+    pub input: T,
+    pub entry: Option<ItemMapEntry<T>>,
+    pub output: T,
+}
+
+/// One map applied to a range of items, split into the segments each entry covers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RangeTraceStep<T = u64> {
+    pub source: String,
+    pub destination: String,
+    pub segments: Vec<TraceSegment<T>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceSegment<T = u64> {
+    pub input: RangeInclusive<T>,
+    pub entry: Option<ItemMapEntry<T>>,
+    pub output: RangeInclusive<T>,
+}
+
+pub fn trace<T: Unsigned>(route: &Route<T>, item: T) -> Vec<TraceStep<T>> {
+    let mut input = item;
+    route
+        .maps()
+        .iter()
+        .map(|map| {
+            let entry = map.find_entry(&input).copied();
+            let output = map.get(&input);
+            let step = TraceStep {
+                source: map.source().to_string(),
+                destination: map.destination().to_string(),
+                input,
+                entry,
+                output,
+            };
+            input = output;
+            step
+        })
+        .collect()
+}
+
+pub fn trace_range<T: Unsigned>(
+    route: &Route<T>,
+    items: RangeInclusive<T>,
+) -> Vec<RangeTraceStep<T>> {
+    let mut inputs = vec![items];
+    route
+        .maps()
+        .iter()
+        .map(|map| {
+            let segments: Vec<TraceSegment<T>> = inputs
+                .iter()
+                .flat_map(|range| map.segments(range.clone()))
+                .map(|(input, entry)| {
+                    let output = match entry {
+                        Some(entry) => entry.get(input.start())..=entry.get(input.end()),
+                        None => input.clone(),
+                    };
+                    TraceSegment {
+                        input,
+                        entry: entry.copied(),
+                        output,
+                    }
+                })
+                .collect();
+            inputs = segments
+                .iter()
+                .map(|segment| segment.output.clone())
+                .collect();
+            RangeTraceStep {
+                source: map.source().to_string(),
+                destination: map.destination().to_string(),
+                segments,
+            }
+        })
+        .collect()
+}
+
+pub fn format_table<T: Unsigned>(steps: &[TraceStep<T>]) -> String {
+    let rows: Vec<[String; 4]> = steps
+        .iter()
+        .map(|step| {
+            [
+                format!("{}-to-{}", step.source, step.destination),
+                step.input.to_string(),
+                format_entry(&step.entry),
+                step.output.to_string(),
+            ]
+        })
+        .collect();
+    table(["Stage", "Input", "Entry", "Output"], rows)
+}
+
+pub fn format_range_table<T: Unsigned>(steps: &[RangeTraceStep<T>]) -> String {
+    let rows: Vec<[String; 4]> = steps
+        .iter()
+        .flat_map(|step| {
+            step.segments.iter().map(|segment| {
+                [
+                    format!("{}-to-{}", step.source, step.destination),
+                    format_range(&segment.input),
+                    format_entry(&segment.entry),
+                    format_range(&segment.output),
+                ]
+            })
+        })
+        .collect();
+    table(["Stage", "Input", "Entry", "Output"], rows)
+}
+
+fn format_entry<T: Unsigned>(entry: &Option<ItemMapEntry<T>>) -> String {
+    match entry {
+        Some(entry) => entry.to_string(),
+        None => "identity".to_string(),
+    }
+}
+
+fn format_range<T: Unsigned>(range: &RangeInclusive<T>) -> String {
+    format!("{}..={}", range.start(), range.end())
+}
+
+fn table(headers: [&str; 4], rows: Vec<[String; 4]>) -> String {
+    let mut widths = headers.map(str::len);
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut table = String::new();
+    let headers = headers.map(str::to_string);
+    for row in std::iter::once(&headers).chain(rows.iter()) {
+        let line = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join("  ");
+        _ = writeln!(table, "{}", line.trim_end());
+    }
+    table
+}
+
+#[cfg(test)]
+mod a_trace {
+    use crate::almanac::Almanac;
+
+    use super::*;
+
+    const TEST_DATA: &str = include_str!("../res/test_data.txt");
+
+    #[test]
+    fn follows_a_seed_through_every_stage() {
+        let almanac: Almanac = TEST_DATA.parse().unwrap();
+        let route = almanac.seed_to_location().unwrap();
+        let steps = trace(&route, 79);
+
+        let values: Vec<(u64, u64)> = steps.iter().map(|step| (step.input, step.output)).collect();
+        assert_eq!(
+            vec![
+                (79, 81),
+                (81, 81),
+                (81, 81),
+                (81, 74),
+                (74, 78),
+                (78, 78),
+                (78, 82)
+            ],
+            values
+        );
+        assert_eq!(Some(ItemMapEntry::from_line("52 50 48")), steps[0].entry);
+        assert_eq!(None, steps[1].entry);
+        assert_eq!("seed", steps[0].source);
+        assert_eq!("location", steps[6].destination);
+    }
+
+    #[test]
+    fn follows_a_seed_range_through_every_stage() {
+        let almanac: Almanac = TEST_DATA.parse().unwrap();
+        let route = almanac.seed_to_location().unwrap();
+        let steps = trace_range(&route, 79..=92);
+
+        assert_eq!(7, steps.len());
+        let final_outputs: Vec<RangeInclusive<u64>> = steps[6]
+            .segments
+            .iter()
+            .map(|segment| segment.output.clone())
+            .collect();
+        for seed in 79..=92 {
+            let location = route.map_continuous(&seed);
+            assert!(final_outputs.iter().any(|range| range.contains(&location)));
+        }
+    }
+
+    #[test]
+    fn can_be_formatted_as_a_table() {
+        let almanac: Almanac = TEST_DATA.parse().unwrap();
+        let route = almanac.route("seed", "fertilizer").unwrap();
+        let expected = "Stage               Input  Entry     Output
+seed-to-soil        79     52 50 48  81
+soil-to-fertilizer  81     identity  81
+";
+        assert_eq!(expected, format_table(&trace(&route, 79)));
+    }
+
+    #[test]
+    fn can_be_serialized_as_json() {
+        let almanac: Almanac = TEST_DATA.parse().unwrap();
+        let route = almanac.route("seed", "soil").unwrap();
+        let json = serde_json::to_string(&trace(&route, 98)).unwrap();
+        let expected = r#"[{"source":"seed","destination":"soil","input":98,"entry":{"destination_range_start":50,"source_range_start":98,"range_length":2},"output":50}]"#;
+        assert_eq!(expected, json);
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+/// The unsigned integer widths that item maps can be built over.
+///
+/// Plain `+` and `-` are only used for offsets inside ranges that have already been checked,
+/// every range boundary is computed with the checked operations.
+pub trait Unsigned:
+    Copy
+    + Ord
+    + Hash
+    + Debug
+    + Display
+    + FromStr<Err = std::num::ParseIntError>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Serialize
+    + Send
+    + Sync
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MAX: Self;
+    const NAME: &'static str;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Unsigned for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const MAX: Self = <$t>::MAX;
+                const NAME: &'static str = stringify!($t);
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128);
+
+#[cfg(test)]
+mod each_width {
+    use crate::almanac::Almanac;
+    use crate::item_map::{ItemMap, ItemMapEntry};
+    use crate::unsigned::Unsigned;
+
+    const TEST_DATA: &str = include_str!("../res/test_data.txt");
+
+    fn maps_the_test_data<T: Unsigned + From<u8>>() {
+        let almanac: Almanac<T> = TEST_DATA.parse().unwrap();
+        let route = almanac.seed_to_location().unwrap();
+        let composed = route.composed();
+        for (seed, location) in [(79, 82), (14, 43), (55, 86), (13, 35)] {
+            assert_eq!(T::from(location), route.map_continuous(&T::from(seed)));
+            assert_eq!(T::from(location), composed.get(&T::from(seed)));
+            assert!(route
+                .inverse_get(&T::from(location))
+                .contains(&T::from(seed)));
+        }
+    }
+
+    fn maps_items_near_the_largest_value<T: Unsigned + From<u8>>() {
+        let two = T::ONE + T::ONE;
+        let entry = ItemMapEntry {
+            destination_range_start: T::ZERO,
+            source_range_start: T::MAX - T::ONE,
+            range_length: two,
+        };
+        let map = ItemMap::new("a-to-a map".to_string(), vec![entry]).unwrap();
+        assert_eq!(T::ZERO, map.get(&(T::MAX - T::ONE)));
+        assert_eq!(T::ONE, map.get(&T::MAX));
+        assert_eq!(T::MAX - two, map.get(&(T::MAX - two)));
+
+        // Applying the map twice sends MAX - 1 to 0 and then leaves it there
+        let composed = map.compose(&map).unwrap();
+        assert_eq!(T::ZERO, composed.get(&(T::MAX - T::ONE)));
+        assert_eq!(T::ONE, composed.get(&T::MAX));
+        assert_eq!(
+            vec![T::ZERO..=T::ONE, T::MAX - two..=T::MAX - two],
+            map.map_range(T::MAX - two..=T::MAX)
+        );
+    }
+
+    fn rejects_entries_past_the_largest_value<T: Unsigned>() {
+        let entry = ItemMapEntry {
+            destination_range_start: T::ZERO,
+            source_range_start: T::MAX,
+            range_length: T::ONE + T::ONE,
+        };
+        let err = ItemMap::new("a-to-b map".to_string(), vec![entry]).unwrap_err();
+        assert!(err.contains(T::NAME), "{}", err);
+    }
+
+    #[test]
+    fn u8_almanacs_work() {
+        maps_the_test_data::<u8>();
+        maps_items_near_the_largest_value::<u8>();
+        rejects_entries_past_the_largest_value::<u8>();
+    }
+
+    #[test]
+    fn u16_almanacs_work() {
+        maps_the_test_data::<u16>();
+        maps_items_near_the_largest_value::<u16>();
+        rejects_entries_past_the_largest_value::<u16>();
+    }
+
+    #[test]
+    fn u32_almanacs_work() {
+        maps_the_test_data::<u32>();
+        maps_items_near_the_largest_value::<u32>();
+        rejects_entries_past_the_largest_value::<u32>();
+    }
+
+    #[test]
+    fn u64_almanacs_work() {
+        maps_the_test_data::<u64>();
+        maps_items_near_the_largest_value::<u64>();
+        rejects_entries_past_the_largest_value::<u64>();
+    }
+
+    #[test]
+    fn u128_almanacs_work() {
+        maps_the_test_data::<u128>();
+        maps_items_near_the_largest_value::<u128>();
+        rejects_entries_past_the_largest_value::<u128>();
+    }
+
+    #[test]
+    fn u8_almanacs_reject_values_too_large_for_u8() {
+        let input = "seeds: 256\n\nseed-to-soil map:\n1 2 3";
+        let err = input.parse::<Almanac<u8>>().unwrap_err();
+        assert!(err.contains("u8"), "{}", err);
+        assert!(input.parse::<Almanac<u16>>().is_ok());
+
+        // Every value fits, but the source range runs past u8::MAX
+        let input = "seeds: 255\n\nseed-to-soil map:\n0 254 3";
+        let err = input.parse::<Almanac<u8>>().unwrap_err();
+        assert!(err.contains("u8"), "{}", err);
+        assert!(input.parse::<Almanac<u16>>().is_ok());
+    }
+
+    #[test]
+    fn u16_almanacs_reject_values_too_large_for_u16() {
+        let input = "seeds: 65536\n\nseed-to-soil map:\n1 2 3";
+        let err = input.parse::<Almanac<u16>>().unwrap_err();
+        assert!(err.contains("u16"), "{}", err);
+        assert!(input.parse::<Almanac<u32>>().is_ok());
+
+        let input = "seeds: 65535\n\nseed-to-soil map:\n65534 0 3";
+        let err = input.parse::<Almanac<u16>>().unwrap_err();
+        assert!(err.contains("u16"), "{}", err);
+        assert!(input.parse::<Almanac<u32>>().is_ok());
+    }
+
+    #[test]
+    fn u32_almanacs_reject_values_too_large_for_u32() {
+        let input = "seeds: 4294967296\n\nseed-to-soil map:\n1 2 3";
+        let err = input.parse::<Almanac<u32>>().unwrap_err();
+        assert!(err.contains("u32"), "{}", err);
+        assert!(input.parse::<Almanac<u64>>().is_ok());
+    }
+
+    #[test]
+    fn u128_almanacs_accept_values_too_large_for_u64() {
+        let input =
+            "seeds: 36893488147419103232\n\nseed-to-location map:\n0 36893488147419103232 5";
+        let almanac: Almanac<u128> = input.parse().unwrap();
+        let route = almanac.seed_to_location().unwrap();
+        assert_eq!(0, route.map_continuous(&almanac.seeds[0]));
+    }
+}
@@ -1,6 +1,6 @@
 use std::{env, fs};
 
-use day_06::operations::*;
+use day_06::race::BigRace;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -9,21 +9,11 @@ fn main() {
     println!("Reading from file '{}'", file_path);
 
     let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let mut lines = contents.lines();
-
-    // Get times
-    let time = lines.next().expect("Input had no times line");
-    let time = value_from_line(time["Time:".len()..].trim());
-    let distance = lines.next().expect("Input had no distances line");
-    let distance = value_from_line(distance["Distance:".len()..].trim());
-    let race = (time, distance);
-    let result = distances_greater_than_record(race.0, race.1).count();
+    let race = match BigRace::parse_kerned(&contents) {
+        Ok(race) => race,
+        Err(err) => panic!("{}", err),
+    };
+    let result = race.ways_to_win();
 
     println!("Result = {}", result);
 }
-
-fn value_from_line(line: &str) -> u64 {
-    line.replace(" ", "")
-        .parse::<u64>()
-        .expect("Error parsing value from line")
-}
@@ -0,0 +1,37 @@
+use std::{env, fs};
+
+use day_06::plot::Curve;
+use day_06::race::Race;
+
+const MAX_POINTS: usize = 1000;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 3 {
+        panic!("Usage: plot <file> <output prefix> [--kerned]");
+    }
+
+    let file_path = &args[1];
+    let output_prefix = &args[2];
+    let kerned = args.iter().skip(3).any(|arg| arg == "--kerned");
+    println!("Reading from file '{}'", file_path);
+
+    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
+    let races = match kerned {
+        true => Race::parse_kerned(&contents).map(|race| vec![race]),
+        false => Race::parse_sheet(&contents),
+    };
+    let races = match races {
+        Ok(races) => races,
+        Err(err) => panic!("{}", err),
+    };
+
+    for (i, race) in races.iter().enumerate() {
+        let curve = Curve::sample(race, MAX_POINTS);
+        for (extension, contents) in [("csv", curve.to_csv()), ("svg", curve.to_svg())] {
+            let path = format!("{}-{}.{}", output_prefix, i + 1, extension);
+            fs::write(&path, contents).expect("Should have been able to write the file");
+            println!("Wrote '{}'", path);
+        }
+    }
+}
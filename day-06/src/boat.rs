@@ -0,0 +1,229 @@
+use std::ops::RangeInclusive;
+
+use crate::operations::winning_hold_times;
+use crate::race::Race;
+
+/// How far a boat travels for a given hold time.
+///
+/// The counting methods assume the distance rises strictly up to the optimal hold time and never
+/// rises again after it. Models that can solve a race directly override
+/// [`BoatModel::closed_form_winning_hold_times`], otherwise the curve is binary searched.
+pub trait BoatModel {
+    fn distance(&self, hold_time: u64, total_time: u64) -> u128;
+
+    /// Every hold time that beats the record, or `None` if the model has no closed form.
+    fn closed_form_winning_hold_times(&self, _race: &Race) -> Option<RangeInclusive<u64>> {
+        None
+    }
+
+    /// The hold time giving the furthest distance. When hold times tie, the shortest one.
+    fn optimal_hold_time(&self, total_time: u64) -> u64 {
+        // The first hold time that does no better than the next one is the peak
+        let (mut low, mut high) = (0, total_time);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.distance(middle, total_time) >= self.distance(middle + 1, total_time) {
+                true => high = middle,
+                false => low = middle + 1,
+            }
+        }
+        low
+    }
+
+    fn max_distance(&self, total_time: u64) -> u128 {
+        self.distance(self.optimal_hold_time(total_time), total_time)
+    }
+
+    /// Every hold time that beats the record. Empty if the record can not be beaten.
+    fn winning_hold_times(&self, race: &Race) -> RangeInclusive<u64> {
+        if let Some(hold_times) = self.closed_form_winning_hold_times(race) {
+            return hold_times;
+        }
+        let record = race.record as u128;
+        let optimal = self.optimal_hold_time(race.time);
+        if self.distance(optimal, race.time) <= record {
+            return RangeInclusive::new(1, 0);
+        }
+        // The first winning hold time, searching the rising side of the curve
+        let (mut low, mut high) = (0, optimal);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.distance(middle, race.time) > record {
+                true => high = middle,
+                false => low = middle + 1,
+            }
+        }
+        let first = low;
+        // The last winning hold time, searching the falling side of the curve
+        let (mut low, mut high) = (optimal, race.time);
+        while low < high {
+            let middle = high - (high - low) / 2;
+            match self.distance(middle, race.time) > record {
+                true => low = middle,
+                false => high = middle - 1,
+            }
+        }
+        first..=low
+    }
+
+    fn ways_to_win(&self, race: &Race) -> u64 {
+        let hold_times = self.winning_hold_times(race);
+        match hold_times.is_empty() {
+            true => 0,
+            false => hold_times.end() - hold_times.start() + 1,
+        }
+    }
+}
+
+/// A boat that charges while the button is held and then moves for the rest of the race.
+///
+/// The default boat is the puzzle's, gaining one unit of speed per millisecond held with no
+/// speed cap, startup delay or drag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Boat {
+    /// Speed gained per millisecond the button is held.
+    pub charge_rate: u64,
+    /// Speed the boat can not charge past.
+    pub max_speed: Option<u64>,
+    /// Milliseconds after release before the boat starts moving.
+    pub startup_delay: u64,
+    /// Speed lost each millisecond the boat moves, until it stops.
+    pub drag: u64,
+}
+
+impl Default for Boat {
+    fn default() -> Self {
+        Boat {
+            charge_rate: 1,
+            max_speed: None,
+            startup_delay: 0,
+            drag: 0,
+        }
+    }
+}
+
+impl BoatModel for Boat {
+    /// Saturates at `u128::MAX` rather than overflowing.
+    fn distance(&self, hold_time: u64, total_time: u64) -> u128 {
+        let move_time = total_time
+            .saturating_sub(hold_time)
+            .saturating_sub(self.startup_delay) as u128;
+        let mut speed = self.charge_rate as u128 * hold_time as u128;
+        if let Some(max_speed) = self.max_speed {
+            speed = speed.min(max_speed as u128);
+        }
+        if self.drag == 0 {
+            return speed.saturating_mul(move_time);
+        }
+        // Speed falls by the drag each millisecond, so the distance is an arithmetic series
+        let drag = self.drag as u128;
+        let steps = move_time.min(speed.div_ceil(drag));
+        let lost = drag.saturating_mul(steps * steps.saturating_sub(1) / 2);
+        steps.saturating_mul(speed) - lost
+    }
+
+    fn closed_form_winning_hold_times(&self, race: &Race) -> Option<RangeInclusive<u64>> {
+        if self.max_speed.is_some() || self.drag != 0 {
+            return None;
+        }
+        if self.charge_rate == 0 {
+            return Some(RangeInclusive::new(1, 0));
+        }
+        // rate * h * (t - h) beats the record exactly when h * (t - h) beats record / rate
+        Some(winning_hold_times(
+            race.time.saturating_sub(self.startup_delay),
+            race.record / self.charge_rate,
+        ))
+    }
+}
+
+impl<F: Fn(u64, u64) -> u128> BoatModel for F {
+    fn distance(&self, hold_time: u64, total_time: u64) -> u128 {
+        self(hold_time, total_time)
+    }
+}
+
+#[cfg(test)]
+mod a_boat {
+    use rstest::rstest;
+
+    use super::{Boat, BoatModel};
+    use crate::race::Race;
+
+    fn count_every_hold_time<M: BoatModel>(model: &M, race: &Race) -> u64 {
+        (0..=race.time)
+            .filter(|hold_time| model.distance(*hold_time, race.time) > race.record as u128)
+            .count() as u64
+    }
+
+    #[rstest]
+    #[case(Race::new(7, 9), 2..=5)]
+    #[case(Race::new(15, 40), 4..=11)]
+    #[case(Race::new(30, 200), 11..=19)]
+    fn defaults_to_the_puzzle_boat(
+        #[case] race: Race,
+        #[case] hold_times: std::ops::RangeInclusive<u64>,
+    ) {
+        assert_eq!(hold_times, Boat::default().winning_hold_times(&race));
+        assert_eq!(
+            race.winning_hold_times(),
+            Boat::default().winning_hold_times(&race)
+        );
+    }
+
+    #[rstest]
+    #[case(Boat { charge_rate: 3, ..Boat::default() }, 63)]
+    #[case(Boat { max_speed: Some(2), ..Boat::default() }, 6)]
+    #[case(Boat { startup_delay: 2, ..Boat::default() }, 7)]
+    #[case(Boat { drag: 1, ..Boat::default() }, 18)]
+    #[case(Boat { drag: 2, ..Boat::default() }, 15)]
+    #[case(Boat { drag: 10, ..Boat::default() }, 7)]
+    #[case(Boat { charge_rate: 0, ..Boat::default() }, 0)]
+    fn can_get_distances(#[case] boat: Boat, #[case] distance: u128) {
+        // Holding for 7ms of a 10ms race leaves 3ms to move
+        assert_eq!(distance, boat.distance(7, 10));
+    }
+
+    #[rstest]
+    #[case(Boat { charge_rate: 3, ..Boat::default() })]
+    #[case(Boat { max_speed: Some(9), ..Boat::default() })]
+    #[case(Boat { startup_delay: 4, ..Boat::default() })]
+    #[case(Boat { drag: 2, ..Boat::default() })]
+    #[case(Boat { charge_rate: 5, max_speed: Some(40), startup_delay: 3, drag: 4 })]
+    #[case(Boat { charge_rate: 0, ..Boat::default() })]
+    fn agrees_with_counting_every_hold_time(#[case] boat: Boat) {
+        for time in 0..40 {
+            for record in (0..400).step_by(7) {
+                let race = Race::new(time, record);
+                assert_eq!(
+                    count_every_hold_time(&boat, &race),
+                    boat.ways_to_win(&race),
+                    "{:?} {:?}",
+                    boat,
+                    race
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn searches_models_without_a_closed_form() {
+        let race = Race::new(30, 200);
+        let model =
+            |hold_time: u64, total_time: u64| (hold_time * (total_time - hold_time)) as u128;
+        assert_eq!(None, model.closed_form_winning_hold_times(&race));
+        assert_eq!(race.winning_hold_times(), model.winning_hold_times(&race));
+        assert_eq!(15, model.optimal_hold_time(race.time));
+        assert_eq!(225, model.max_distance(race.time));
+    }
+
+    #[test]
+    fn finds_the_peak_of_a_capped_boat() {
+        let boat = Boat {
+            max_speed: Some(5),
+            ..Boat::default()
+        };
+        assert_eq!(5, boat.optimal_hold_time(30));
+        assert_eq!(125, boat.max_distance(30));
+    }
+}
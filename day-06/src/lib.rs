@@ -1 +1,4 @@
+pub mod boat;
 pub mod operations;
+pub mod plot;
+pub mod race;
@@ -1,13 +1,86 @@
-pub fn distance(hold_time: u64, total_time: u64) -> u64 {
+use std::ops::RangeInclusive;
+
+use num_bigint::BigUint;
+
+/// Widened so that it can not overflow for any `u64` race.
+pub fn distance(hold_time: u64, total_time: u64) -> u128 {
+    let move_time = (total_time - hold_time) as u128;
+    let speed = hold_time as u128;
+    speed * move_time
+}
+
+pub fn big_distance(hold_time: &BigUint, total_time: &BigUint) -> BigUint {
     let move_time = total_time - hold_time;
     let speed = hold_time;
     speed * move_time
 }
 
-pub fn iterate_distances(total_time: u64) -> impl Iterator<Item = u64> {
+pub fn iterate_distances(total_time: u64) -> impl Iterator<Item = u128> {
     (0..total_time).map(move |hold_time| distance(hold_time, total_time))
 }
 
-pub fn distances_greater_than_record(total_time: u64, record_distance: u64) -> impl Iterator {
-    iterate_distances(total_time).filter(move |distance| *distance > record_distance)
+pub fn distances_greater_than_record(
+    total_time: u64,
+    record_distance: u64,
+) -> impl Iterator<Item = u128> {
+    iterate_distances(total_time).filter(move |distance| *distance > record_distance as u128)
+}
+
+/// Every hold time that beats the record, from the roots of `h * (t - h) = record`.
+/// Empty if the record can not be beaten.
+pub fn winning_hold_times(total_time: u64, record_distance: u64) -> RangeInclusive<u64> {
+    let squared_time = total_time as u128 * total_time as u128;
+    let four_records = record_distance as u128 * 4;
+    if squared_time <= four_records {
+        return RangeInclusive::new(1, 0);
+    }
+    // As in `big_winning_hold_times`, the floored root can only leave the estimate low
+    let root = (squared_time - four_records).isqrt() as u64;
+    let optimal = total_time / 2;
+    let mut first = (total_time - root) / 2;
+    while distance(first, total_time) <= record_distance as u128 {
+        if first >= optimal {
+            return RangeInclusive::new(1, 0);
+        }
+        first += 1;
+    }
+    first..=total_time - first
+}
+
+/// Exact integer square root, the largest `r` with `r * r <= n`.
+pub fn isqrt(n: &BigUint) -> BigUint {
+    n.sqrt()
+}
+
+/// Every hold time that beats the record, from the roots of `h * (t - h) = record`.
+/// `None` if the record can not be beaten.
+pub fn big_winning_hold_times(
+    total_time: &BigUint,
+    record_distance: &BigUint,
+) -> Option<RangeInclusive<BigUint>> {
+    let squared_time = total_time * total_time;
+    let four_records = record_distance * 4_u32;
+    if squared_time <= four_records {
+        return None;
+    }
+    // The lower root is (t - sqrt(d)) / 2. Flooring the square root can leave the estimate
+    // one or two below the first winning hold time, never above it.
+    let root = isqrt(&(squared_time - four_records));
+    let optimal = total_time / 2_u32;
+    let mut first = (total_time - root) / 2_u32;
+    while big_distance(&first, total_time) <= *record_distance {
+        if first >= optimal {
+            return None;
+        }
+        first += 1_u32;
+    }
+    let last = total_time - &first;
+    Some(first..=last)
+}
+
+pub fn big_ways_to_win(total_time: &BigUint, record_distance: &BigUint) -> BigUint {
+    match big_winning_hold_times(total_time, record_distance) {
+        Some(hold_times) => hold_times.end() - hold_times.start() + 1_u32,
+        None => BigUint::default(),
+    }
 }
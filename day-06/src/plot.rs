@@ -0,0 +1,240 @@
+use std::fmt::Write;
+
+use crate::operations::distance;
+use crate::race::Race;
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 400.0;
+const MARGIN: f64 = 60.0;
+
+/// Distance against hold time for a race, sampled for plotting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Curve {
+    pub race: Race,
+    /// Hold times in increasing order with their distances.
+    pub points: Vec<(u64, u128)>,
+}
+
+impl Curve {
+    /// Takes every hold time from 0 to the race's time when there are at most `max_points` of
+    /// them, otherwise `max_points` evenly spaced hold times over the same span plus the optimal
+    /// one so that the peak is never lost.
+    pub fn sample(race: &Race, max_points: usize) -> Curve {
+        let max_points = max_points.max(2) as u64;
+        let points = match race.time < max_points {
+            true => (0..=race.time)
+                .map(|hold_time| (hold_time, distance(hold_time, race.time)))
+                .collect(),
+            false => {
+                let mut hold_times: Vec<u64> = (0..max_points)
+                    .map(|i| (race.time as u128 * i as u128 / (max_points - 1) as u128) as u64)
+                    .collect();
+                hold_times.push(race.optimal_hold_time());
+                hold_times.sort();
+                hold_times.dedup();
+                hold_times
+                    .into_iter()
+                    .map(|hold_time| (hold_time, distance(hold_time, race.time)))
+                    .collect()
+            }
+        };
+        Curve {
+            race: *race,
+            points,
+        }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("hold_time,distance,beats_record\n");
+        for (hold_time, distance) in self.points.iter() {
+            let beats_record = *distance > self.race.record as u128;
+            _ = writeln!(csv, "{},{},{}", hold_time, distance, beats_record);
+        }
+        csv
+    }
+
+    /// A standalone chart with the record line, the winning region shaded and the optimal hold
+    /// time annotated.
+    pub fn to_svg(&self) -> String {
+        let max_hold_time = self.race.time.max(1) as f64;
+        let max_distance = self
+            .race
+            .wide_max_distance()
+            .max(self.race.record as u128)
+            .max(1) as f64;
+        let x = |hold_time: u64| MARGIN + hold_time as f64 / max_hold_time * (WIDTH - 2.0 * MARGIN);
+        let y = |distance: u128| {
+            HEIGHT - MARGIN - distance as f64 / max_distance * (HEIGHT - 2.0 * MARGIN)
+        };
+        let (top, bottom) = (y(max_distance as u128), y(0));
+
+        let mut svg = String::new();
+        _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = WIDTH,
+            h = HEIGHT
+        );
+        _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+
+        let winning_hold_times = self.race.winning_hold_times();
+        if !winning_hold_times.is_empty() {
+            let (start, end) = (x(*winning_hold_times.start()), x(*winning_hold_times.end()));
+            _ = writeln!(
+                svg,
+                r##"<rect class="winning" x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="#c8e6c9"/>"##,
+                start,
+                top,
+                end - start,
+                bottom - top
+            );
+        }
+
+        _ = writeln!(
+            svg,
+            r#"<line class="axis" x1="{m:.1}" y1="{b:.1}" x2="{r:.1}" y2="{b:.1}" stroke="black"/>"#,
+            m = MARGIN,
+            b = bottom,
+            r = WIDTH - MARGIN
+        );
+        _ = writeln!(
+            svg,
+            r#"<line class="axis" x1="{m:.1}" y1="{t:.1}" x2="{m:.1}" y2="{b:.1}" stroke="black"/>"#,
+            m = MARGIN,
+            t = top,
+            b = bottom
+        );
+        _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">hold time (ms), 0 to {}</text>"#,
+            WIDTH / 2.0,
+            HEIGHT - MARGIN / 3.0,
+            self.race.time
+        );
+        _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}">distance (mm), up to {}</text>"#,
+            MARGIN,
+            MARGIN / 2.0,
+            max_distance as u128
+        );
+
+        let points: Vec<String> = self
+            .points
+            .iter()
+            .map(|(hold_time, distance)| format!("{:.1},{:.1}", x(*hold_time), y(*distance)))
+            .collect();
+        _ = writeln!(
+            svg,
+            r#"<polyline class="distance" points="{}" fill="none" stroke="steelblue" stroke-width="2"/>"#,
+            points.join(" ")
+        );
+
+        let record = y(self.race.record as u128);
+        _ = writeln!(
+            svg,
+            r#"<line class="record" x1="{:.1}" y1="{r:.1}" x2="{:.1}" y2="{r:.1}" stroke="red" stroke-dasharray="6 4"/>"#,
+            MARGIN,
+            WIDTH - MARGIN,
+            r = record
+        );
+
+        let optimal = self.race.optimal_hold_time();
+        let peak = self.race.wide_max_distance();
+        _ = writeln!(
+            svg,
+            r#"<line class="optimal" x1="{o:.1}" y1="{:.1}" x2="{o:.1}" y2="{:.1}" stroke="gray" stroke-dasharray="2 2"/>"#,
+            y(peak),
+            bottom,
+            o = x(optimal)
+        );
+        _ = writeln!(
+            svg,
+            r#"<text class="optimal" x="{:.1}" y="{:.1}" text-anchor="middle">optimal hold {}ms, {}mm</text>"#,
+            x(optimal),
+            y(peak) - 8.0,
+            optimal,
+            peak
+        );
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod a_curve {
+    use super::Curve;
+    use crate::race::Race;
+
+    #[test]
+    fn takes_every_hold_time_of_a_short_race() {
+        let curve = Curve::sample(&Race::new(7, 9), 100);
+        assert_eq!(
+            vec![
+                (0, 0),
+                (1, 6),
+                (2, 10),
+                (3, 12),
+                (4, 12),
+                (5, 10),
+                (6, 6),
+                (7, 0)
+            ],
+            curve.points
+        );
+    }
+
+    #[test]
+    fn covers_the_same_hold_times_on_either_side_of_downsampling() {
+        let race = Race::new(7, 9);
+        let every = Curve::sample(&race, 8);
+        let sampled = Curve::sample(&race, 7);
+        assert_eq!(8, every.points.len());
+        assert_eq!(every.points.first(), sampled.points.first());
+        assert_eq!(every.points.last(), sampled.points.last());
+        assert!(sampled
+            .points
+            .iter()
+            .all(|point| every.points.contains(point)));
+    }
+
+    #[test]
+    fn downsamples_a_long_race_keeping_the_peak() {
+        let race = Race::new(71531, 940200);
+        let curve = Curve::sample(&race, 100);
+        assert!(curve.points.len() <= 101, "{}", curve.points.len());
+        assert_eq!(Some(&(0, 0)), curve.points.first());
+        assert_eq!(Some(&(71531, 0)), curve.points.last());
+        assert!(curve.points.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(curve
+            .points
+            .contains(&(race.optimal_hold_time(), race.wide_max_distance())));
+    }
+
+    #[test]
+    fn can_be_exported_as_csv() {
+        let csv = Curve::sample(&Race::new(7, 9), 100).to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(9, lines.len());
+        assert_eq!("hold_time,distance,beats_record", lines[0]);
+        assert_eq!("1,6,false", lines[2]);
+        assert_eq!("2,10,true", lines[3]);
+    }
+
+    #[test]
+    fn can_be_exported_as_svg() {
+        let svg = Curve::sample(&Race::new(30, 200), 100).to_svg();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains("<rect class=\"winning\""));
+        assert!(svg.contains("<polyline class=\"distance\""));
+        assert!(svg.contains("<line class=\"record\""));
+        assert!(svg.contains("optimal hold 15ms, 225mm"));
+    }
+
+    #[test]
+    fn does_not_shade_an_unbeatable_race() {
+        let svg = Curve::sample(&Race::new(7, 12), 100).to_svg();
+        assert!(!svg.contains("class=\"winning\""));
+    }
+}
@@ -0,0 +1,308 @@
+use std::ops::RangeInclusive;
+
+use num_bigint::BigUint;
+
+use crate::operations::{big_ways_to_win, big_winning_hold_times, distance, winning_hold_times};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u64,
+    pub record: u64,
+}
+
+/// A race whose time or record may not fit in a `u64`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigRace {
+    pub time: BigUint,
+    pub record: BigUint,
+}
+
+impl Race {
+    pub fn new(time: u64, record: u64) -> Self {
+        Race { time, record }
+    }
+
+    /// Reads a sheet as separate races, one per column, as in part 1.
+    pub fn parse_sheet(input: &str) -> Result<Vec<Race>, String> {
+        let (times, records) = Self::sheet_lines(input)?;
+        let times = values_from_line(times)?;
+        let records = values_from_line(records)?;
+        if times.len() != records.len() {
+            return Err(format!(
+                "Sheet has {} times but {} records",
+                times.len(),
+                records.len()
+            ));
+        }
+        Ok(times
+            .into_iter()
+            .zip(records)
+            .map(|(time, record)| Race::new(time, record))
+            .collect())
+    }
+
+    /// Reads a sheet as a single race, ignoring the spaces between digits, as in part 2.
+    pub fn parse_kerned(input: &str) -> Result<Race, String> {
+        let (time, record) = Self::sheet_lines(input)?;
+        Ok(Race::new(kerned_value(time)?, kerned_value(record)?))
+    }
+
+    fn sheet_lines(input: &str) -> Result<(&str, &str), String> {
+        let mut lines = input.lines();
+        let times = lines.next().ok_or("Input had no times line")?;
+        let times = times.strip_prefix("Time:").ok_or(format!(
+            "Times line '{}' does not start with 'Time:'",
+            times
+        ))?;
+        let records = lines.next().ok_or("Input had no distances line")?;
+        let records = records.strip_prefix("Distance:").ok_or(format!(
+            "Distances line '{}' does not start with 'Distance:'",
+            records
+        ))?;
+        Ok((times, records))
+    }
+
+    /// Every hold time that beats the record. Empty if the record can not be beaten.
+    pub fn winning_hold_times(&self) -> RangeInclusive<u64> {
+        winning_hold_times(self.time, self.record)
+    }
+
+    pub fn ways_to_win(&self) -> u64 {
+        let winning_hold_times = self.winning_hold_times();
+        match winning_hold_times.is_empty() {
+            true => 0,
+            false => winning_hold_times.end() - winning_hold_times.start() + 1,
+        }
+    }
+
+    /// The hold time giving the furthest distance. When two hold times tie, the shorter one.
+    pub fn optimal_hold_time(&self) -> u64 {
+        self.time / 2
+    }
+
+    /// Panics if the distance does not fit in a `u64`, see [`Race::wide_max_distance`].
+    pub fn max_distance(&self) -> u64 {
+        let max_distance = self.wide_max_distance();
+        u64::try_from(max_distance)
+            .unwrap_or_else(|_| panic!("Max distance {} overflows u64", max_distance))
+    }
+
+    pub fn wide_max_distance(&self) -> u128 {
+        distance(self.optimal_hold_time(), self.time)
+    }
+}
+
+impl BigRace {
+    pub fn new(time: BigUint, record: BigUint) -> Self {
+        BigRace { time, record }
+    }
+
+    /// Reads a sheet as a single race, ignoring the spaces between digits, as in part 2.
+    pub fn parse_kerned(input: &str) -> Result<BigRace, String> {
+        let (time, record) = Race::sheet_lines(input)?;
+        Ok(BigRace::new(
+            big_kerned_value(time)?,
+            big_kerned_value(record)?,
+        ))
+    }
+
+    /// Every hold time that beats the record. `None` if the record can not be beaten.
+    pub fn winning_hold_times(&self) -> Option<RangeInclusive<BigUint>> {
+        big_winning_hold_times(&self.time, &self.record)
+    }
+
+    pub fn ways_to_win(&self) -> BigUint {
+        big_ways_to_win(&self.time, &self.record)
+    }
+}
+
+impl From<Race> for BigRace {
+    fn from(race: Race) -> Self {
+        BigRace::new(race.time.into(), race.record.into())
+    }
+}
+
+fn values_from_line(line: &str) -> Result<Vec<u64>, String> {
+    line.split_whitespace()
+        .map(|value| {
+            value
+                .parse::<u64>()
+                .map_err(|err| format!("Failed parsing value '{}' as u64, {}", value, err))
+        })
+        .collect()
+}
+
+fn kerned_value(line: &str) -> Result<u64, String> {
+    let value: String = line.split_whitespace().collect();
+    value
+        .parse::<u64>()
+        .map_err(|err| format!("Failed parsing value '{}' as u64, {}", value, err))
+}
+
+fn big_kerned_value(line: &str) -> Result<BigUint, String> {
+    let value: String = line.split_whitespace().collect();
+    value
+        .parse::<BigUint>()
+        .map_err(|err| format!("Failed parsing value '{}' as an integer, {}", value, err))
+}
+
+#[cfg(test)]
+mod a_race {
+    use rstest::rstest;
+
+    use super::Race;
+    use crate::operations::{distance, distances_greater_than_record, iterate_distances};
+
+    const SHEET: &str = "Time:      7  15   30\nDistance:  9  40  200";
+
+    #[test]
+    fn measures_distances_of_races_longer_than_u32_without_wrapping() {
+        let time = (1_u64 << 32) + 6;
+        // Past u32 from the first hold times, and past u64 at the peak of a 2^33 race
+        assert_eq!(6_u128 << 32, distance(1 << 32, time));
+        assert_eq!(1_u128 << 64, distance(1 << 32, 1 << 33));
+        assert_eq!(
+            vec![0, time as u128 - 1, 2 * (time as u128 - 2)],
+            iterate_distances(time).take(3).collect::<Vec<u128>>()
+        );
+        let race = Race::new(1 << 33, u64::MAX);
+        assert_eq!(1_u128 << 64, race.wide_max_distance());
+        assert_eq!(1, race.ways_to_win());
+        assert_eq!(
+            Some(6_u128 << 32),
+            distances_greater_than_record(time, (6 << 32) - 1).next()
+        );
+    }
+
+    #[test]
+    fn can_be_parsed_from_a_spaced_sheet() {
+        let races = Race::parse_sheet(SHEET).unwrap();
+        assert_eq!(
+            vec![Race::new(7, 9), Race::new(15, 40), Race::new(30, 200)],
+            races
+        );
+    }
+
+    #[test]
+    fn can_be_parsed_from_a_kerned_sheet() {
+        assert_eq!(Ok(Race::new(71530, 940200)), Race::parse_kerned(SHEET));
+    }
+
+    #[rstest]
+    #[case("Time: 7 15\nDistance: 9")]
+    #[case("Time: 7\n")]
+    #[case("Times: 7\nDistance: 9")]
+    #[case("Time: x\nDistance: 9")]
+    fn rejects_invalid_sheets(#[case] input: &str) {
+        assert!(Race::parse_sheet(input).is_err());
+    }
+
+    #[rstest]
+    #[case(Race::new(7, 9), 2..=5, 4)]
+    #[case(Race::new(15, 40), 4..=11, 8)]
+    #[case(Race::new(30, 200), 11..=19, 9)]
+    #[case(Race::new(71530, 940200), 14..=71516, 71503)]
+    #[case(Race::new(10_000_000_000, 1), 1..=9_999_999_999, 9_999_999_999)]
+    fn can_get_winning_hold_times(
+        #[case] race: Race,
+        #[case] hold_times: std::ops::RangeInclusive<u64>,
+        #[case] ways_to_win: u64,
+    ) {
+        assert_eq!(hold_times, race.winning_hold_times());
+        assert_eq!(ways_to_win, race.ways_to_win());
+    }
+
+    #[rstest]
+    #[case(Race::new(7, 12))]
+    #[case(Race::new(7, 100))]
+    #[case(Race::new(0, 0))]
+    fn has_no_ways_to_win_an_unbeatable_record(#[case] race: Race) {
+        assert!(race.winning_hold_times().is_empty());
+        assert_eq!(0, race.ways_to_win());
+    }
+
+    #[rstest]
+    #[case(Race::new(7, 9), 3, 12)]
+    #[case(Race::new(30, 200), 15, 225)]
+    fn can_get_the_optimal_hold_time(
+        #[case] race: Race,
+        #[case] hold_time: u64,
+        #[case] max_distance: u64,
+    ) {
+        assert_eq!(hold_time, race.optimal_hold_time());
+        assert_eq!(max_distance, race.max_distance());
+    }
+}
+
+#[cfg(test)]
+mod a_big_race {
+    use num_bigint::BigUint;
+    use rstest::rstest;
+
+    use super::{BigRace, Race};
+
+    #[test]
+    fn can_be_parsed_from_a_kerned_sheet_that_overflows_u64() {
+        let sheet = "Time: 123456789 123456789 123\nDistance: 99";
+        let race = BigRace::parse_kerned(sheet).unwrap();
+        assert_eq!(
+            "123456789123456789123".parse::<BigUint>().unwrap(),
+            race.time
+        );
+        assert_eq!(BigUint::from(99_u32), race.record);
+        assert!(Race::parse_kerned(sheet).is_err());
+    }
+
+    #[rstest]
+    #[case(7, 9)]
+    #[case(15, 40)]
+    #[case(30, 200)]
+    #[case(71530, 940200)]
+    #[case(7, 12)]
+    #[case(0, 0)]
+    #[case(1, 0)]
+    #[case(2, 0)]
+    fn agrees_with_a_race(#[case] time: u64, #[case] record: u64) {
+        let race = Race::new(time, record);
+        let big_race = BigRace::from(race);
+        assert_eq!(BigUint::from(race.ways_to_win()), big_race.ways_to_win());
+        match big_race.winning_hold_times() {
+            Some(hold_times) => assert_eq!(
+                race.winning_hold_times(),
+                u64::try_from(hold_times.start()).unwrap()
+                    ..=u64::try_from(hold_times.end()).unwrap()
+            ),
+            None => assert!(race.winning_hold_times().is_empty()),
+        }
+    }
+
+    #[test]
+    fn agrees_with_counting_every_hold_time() {
+        for time in 0..60 {
+            for record in 0..time * time / 4 + 2 {
+                let brute_force = (0..=time)
+                    .filter(|hold_time| hold_time * (time - hold_time) > record)
+                    .count();
+                let big_race = BigRace::from(Race::new(time, record));
+                assert_eq!(
+                    BigUint::from(brute_force),
+                    big_race.ways_to_win(),
+                    "time {} record {}",
+                    time,
+                    record
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn can_solve_races_past_u64() {
+        // Records just under the optimum leave exactly the optimal hold time winning
+        let time = BigUint::from(10_u32).pow(30);
+        let optimum = &time / 2_u32;
+        let record = &optimum * &optimum - 1_u32;
+        let race = BigRace::new(time, record);
+        assert_eq!(Some(optimum.clone()..=optimum), race.winning_hold_times());
+        assert_eq!(BigUint::from(1_u32), race.ways_to_win());
+    }
+}
@@ -0,0 +1,65 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use day_07::card::Card;
+use day_07::hand::Hand;
+use day_07::play::Play;
+use day_07::ruleset::Ruleset;
+
+const NO_OF_PLAYS: usize = 1_000_000;
+
+fn random_cards(rng: &mut StdRng) -> [Card; 5] {
+    [(); 5].map(|_| Card::ALL[rng.gen_range(0..Card::COUNT)])
+}
+
+fn classify(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(7);
+    let hands: Vec<[Card; 5]> = (0..NO_OF_PLAYS).map(|_| random_cards(&mut rng)).collect();
+    let mut group = c.benchmark_group("classify a million hands");
+    group.sample_size(10);
+    for (name, ruleset) in [("standard", Ruleset::STANDARD), ("joker", Ruleset::JOKER)] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                hands
+                    .iter()
+                    .map(|cards| Hand::with_ruleset(*cards, &ruleset).sort_key() as u64)
+                    .sum::<u64>()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn sort(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(7);
+    let plays: Vec<Play> = (0..NO_OF_PLAYS)
+        .map(|_| Play::new(Hand::new(random_cards(&mut rng)), rng.gen_range(1..1000)))
+        .collect();
+    let mut group = c.benchmark_group("sort a million plays");
+    group.sample_size(10);
+    group.bench_function("by hand", |b| {
+        b.iter_batched(
+            || plays.clone(),
+            |mut plays| {
+                plays.sort();
+                plays
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("by sort key", |b| {
+        b.iter_batched(
+            || plays.clone(),
+            |mut plays| {
+                plays.sort_unstable_by_key(|play| play.hand().sort_key());
+                plays
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, classify, sort);
+criterion_main!(benches);
@@ -0,0 +1,92 @@
+use std::env;
+
+use day_07::card::Card;
+use day_07::equity::{distribution, Deck, Distribution, MAX_EXACT_OUTCOMES};
+use day_07::hand::hand_score::HandScore;
+use day_07::ruleset::Ruleset;
+
+const USAGE: &str =
+    "Usage: equity <known cards, or - for none> <hand size> [--deck <card=count,...>] [--max-exact <n>] [--samples <n>] [--seed <n>]
+Every distribution is exact unless --max-exact is set below its number of completions, when --samples are dealt instead.";
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 3 {
+        panic!("{}", USAGE);
+    }
+
+    let known: Vec<Card> = match args[1].as_str() {
+        "-" => vec![],
+        known => match known.chars().map(Card::try_from).collect() {
+            Ok(known) => known,
+            Err(err) => panic!("{}", err),
+        },
+    };
+    let hand_size = args[2]
+        .parse::<usize>()
+        .unwrap_or_else(|err| panic!("Failed to parse hand size {}, {}", args[2], err));
+
+    let mut deck = Deck::uniform(4);
+    let mut max_exact_outcomes = MAX_EXACT_OUTCOMES;
+    let mut samples = 100_000;
+    let mut seed = 0;
+    let mut options = args[3..].iter();
+    while let Some(option) = options.next() {
+        let value = options
+            .next()
+            .unwrap_or_else(|| panic!("Missing value for {}\n{}", option, USAGE));
+        match option.as_str() {
+            "--deck" => deck = value.parse().unwrap_or_else(|err| panic!("{}", err)),
+            "--max-exact" => {
+                max_exact_outcomes = value.parse().expect("Failed to parse max exact outcomes")
+            }
+            "--samples" => samples = value.parse().expect("Failed to parse samples"),
+            "--seed" => seed = value.parse().expect("Failed to parse seed"),
+            _ => panic!("Unknown option {}\n{}", option, USAGE),
+        }
+    }
+
+    let [standard, joker] = [Ruleset::STANDARD, Ruleset::JOKER].map(|ruleset| {
+        match distribution(
+            &known,
+            hand_size,
+            &deck,
+            &ruleset,
+            max_exact_outcomes,
+            samples,
+            seed,
+        ) {
+            Ok(distribution) => distribution,
+            Err(err) => panic!("{}", err),
+        }
+    });
+    print_table(&standard, &joker);
+}
+
+fn print_table(standard: &Distribution, joker: &Distribution) {
+    let method = |distribution: &Distribution| match distribution.exact {
+        true => "exact",
+        false => "sampled",
+    };
+    println!(
+        "{:<14} {:>18} {:>18}",
+        "Category",
+        format!("standard ({})", method(standard)),
+        format!("joker ({})", method(joker))
+    );
+    let scores = standard
+        .probabilities
+        .keys()
+        .chain(joker.probabilities.keys());
+    let mut scores: Vec<&HandScore> = scores.collect();
+    scores.sort();
+    scores.dedup();
+    for score in scores.into_iter().rev() {
+        println!(
+            "{:<14} {:>18.6} {:>18.6}",
+            format!("{:?}", score),
+            standard.probability(*score),
+            joker.probability(*score)
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::{env, fs};
 
-use day_07::play::Play;
+use day_07::play::{total_winnings, Play};
+use day_07::ruleset::Ruleset;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -8,48 +9,47 @@ fn main() {
     let file_path = &args[1];
     println!("Reading from file '{}'", file_path);
 
+    // An optional ruleset file replaces the standard rules
+    let ruleset = match args.get(2) {
+        Some(ruleset_path) => {
+            let config = fs::read_to_string(ruleset_path)
+                .expect("Should have been able to read the ruleset file");
+            match config.parse::<Ruleset>() {
+                Ok(ruleset) => ruleset,
+                Err(err) => panic!("{}", err),
+            }
+        }
+        None => Ruleset::STANDARD,
+    };
+
     let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
     let plays: Vec<Play> = match contents
         .lines()
-        .map(|input| Play::try_from(input))
+        .map(|input| Play::parse(input, &ruleset))
         .collect()
     {
         Ok(play) => play,
         Err(err) => panic!("{}", err),
     };
 
-    let result = calculate_sum_of_scores(plays);
+    let result = total_winnings(plays);
     println!("Result = {}", result);
 }
 
-fn calculate_sum_of_scores(mut plays: Vec<Play>) -> u32 {
-    plays.sort();
-    let mut rank = 0;
-    let mut sum = 0;
-    let mut iter = plays.into_iter();
-    while let Some(play) = iter.next() {
-        rank += 1;
-        sum += play.bid() * rank;
-    }
-    sum
-}
-
 #[cfg(test)]
 mod tests {
-    use day_07::play::Play;
-
-    use crate::calculate_sum_of_scores;
+    use day_07::play::{total_winnings, Play};
 
     #[test]
     fn example() {
         let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
         let input = input.split('\n');
-        let mut plays: Vec<Play> = match input.map(|input| Play::try_from(input)).collect() {
+        let plays: Vec<Play> = match input.map(Play::try_from).collect() {
             Ok(play) => play,
             Err(err) => panic!("{}", err),
         };
 
-        let sum = calculate_sum_of_scores(plays);
+        let sum = total_winnings(plays);
 
         assert_eq!(6440, sum);
     }
@@ -0,0 +1,45 @@
+use std::{env, fs};
+
+use day_07::play::{total_winnings, Play};
+use day_07::ruleset::Ruleset;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let file_path = &args[1];
+    println!("Reading from file '{}'", file_path);
+
+    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
+    let plays: Vec<Play> = match contents
+        .lines()
+        .map(|input| Play::parse(input, &Ruleset::JOKER))
+        .collect()
+    {
+        Ok(play) => play,
+        Err(err) => panic!("{}", err),
+    };
+
+    let result = total_winnings(plays);
+    println!("Result = {}", result);
+}
+
+#[cfg(test)]
+mod tests {
+    use day_07::play::{total_winnings, Play};
+    use day_07::ruleset::Ruleset;
+
+    #[test]
+    fn example() {
+        let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
+        let plays: Vec<Play> = match input
+            .lines()
+            .map(|input| Play::parse(input, &Ruleset::JOKER))
+            .collect()
+        {
+            Ok(play) => play,
+            Err(err) => panic!("{}", err),
+        };
+
+        assert_eq!(5905, total_winnings(plays));
+    }
+}
@@ -0,0 +1,52 @@
+use std::{env, fs};
+
+use day_07::play::Play;
+use day_07::report::Report;
+use day_07::ruleset::Ruleset;
+
+const USAGE: &str =
+    "Usage: report <file> [--joker | --ruleset <file>] [--format <table | csv | json>]";
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        panic!("{}", USAGE);
+    }
+
+    let file_path = &args[1];
+    let mut ruleset = Ruleset::STANDARD;
+    let mut format = "table".to_string();
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--joker" => ruleset = Ruleset::JOKER,
+            "--ruleset" => {
+                let path = options.next().expect(USAGE);
+                let config = fs::read_to_string(path)
+                    .expect("Should have been able to read the ruleset file");
+                ruleset = config.parse().unwrap_or_else(|err| panic!("{}", err));
+            }
+            "--format" => format = options.next().expect(USAGE).to_string(),
+            _ => panic!("Unknown option {}\n{}", option, USAGE),
+        }
+    }
+
+    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
+    let plays: Vec<Play> = match contents
+        .lines()
+        .map(|input| Play::parse(input, &ruleset))
+        .collect()
+    {
+        Ok(play) => play,
+        Err(err) => panic!("{}", err),
+    };
+
+    let report = Report::new(plays, &ruleset);
+    let output = match format.as_str() {
+        "table" => report.to_table(),
+        "csv" => report.to_csv(),
+        "json" => report.to_json().unwrap_or_else(|err| panic!("{}", err)),
+        _ => panic!("Unknown format {}\n{}", format, USAGE),
+    };
+    print!("{}", output);
+}
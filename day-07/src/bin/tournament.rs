@@ -0,0 +1,86 @@
+use std::{env, fs};
+
+use day_07::ruleset::Ruleset;
+use day_07::tournament::{simulate, Config, Tournament};
+
+const USAGE: &str =
+    "Usage: tournament [--plays <n>] [--size <n>] [--deck <card=count,...>] [--bids <kind:parameters>] [--seed <n>] [--output <prefix>] [--runs <n>]";
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let mut config = Config::default();
+    let mut seed = 0;
+    let mut output = None;
+    let mut runs = None;
+    let mut options = args[1..].iter();
+    while let Some(option) = options.next() {
+        let value = options
+            .next()
+            .unwrap_or_else(|| panic!("Missing value for {}\n{}", option, USAGE));
+        match option.as_str() {
+            "--plays" => config.plays = value.parse().expect("Failed to parse plays"),
+            "--size" => config.hand_size = value.parse().expect("Failed to parse hand size"),
+            "--deck" => config.deck = value.parse().unwrap_or_else(|err| panic!("{}", err)),
+            "--bids" => config.bids = value.parse().unwrap_or_else(|err| panic!("{}", err)),
+            "--seed" => seed = value.parse().expect("Failed to parse seed"),
+            "--output" => output = Some(value.to_string()),
+            "--runs" => runs = Some(value.parse::<usize>().expect("Failed to parse runs")),
+            _ => panic!("Unknown option {}\n{}", option, USAGE),
+        }
+    }
+
+    let rulesets = [("standard", Ruleset::STANDARD), ("joker", Ruleset::JOKER)];
+    match runs {
+        // Many tournaments, for the spread of their winnings
+        Some(runs) => {
+            let summaries = match simulate(&config, runs, &rulesets.map(|(_, r)| r), seed) {
+                Ok(summaries) => summaries,
+                Err(err) => panic!("{}", err),
+            };
+            println!(
+                "{:<10} {:>16} {:>20} {:>14} {:>14} {:>14}",
+                "Ruleset", "Mean", "Variance", "Std dev", "Min", "Max"
+            );
+            for ((name, _), summary) in rulesets.iter().zip(summaries.iter()) {
+                println!(
+                    "{:<10} {:>16.1} {:>20.1} {:>14.1} {:>14} {:>14}",
+                    name,
+                    summary.mean,
+                    summary.variance,
+                    summary.std_dev(),
+                    summary.min,
+                    summary.max
+                );
+            }
+        }
+        // One tournament, as an input file with its expected answers
+        None => {
+            let tournament = match Tournament::deal(&config, seed) {
+                Ok(tournament) => tournament,
+                Err(err) => panic!("{}", err),
+            };
+            let mut expected = String::new();
+            for (name, ruleset) in rulesets.iter() {
+                expected.push_str(&format!(
+                    "{}: {}\n",
+                    name,
+                    tournament.total_winnings(ruleset)
+                ));
+            }
+            match output {
+                Some(prefix) => {
+                    let input_path = format!("{}.txt", prefix);
+                    let expected_path = format!("{}-expected.txt", prefix);
+                    fs::write(&input_path, tournament.to_input(&Ruleset::STANDARD))
+                        .expect("Should have been able to write the input file");
+                    fs::write(&expected_path, &expected)
+                        .expect("Should have been able to write the expected file");
+                    println!("Wrote '{}' and '{}'", input_path, expected_path);
+                }
+                None => print!("{}", tournament.to_input(&Ruleset::STANDARD)),
+            }
+            eprint!("{}", expected);
+        }
+    }
+}
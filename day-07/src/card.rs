@@ -1,4 +1,11 @@
-use std::{collections::HashMap, fmt};
+pub mod suited;
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::ruleset::Ruleset;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Card {
@@ -18,39 +25,68 @@ pub enum Card {
 }
 
 impl Card {
-    fn face_cards_map() -> HashMap<char, Card> {
-        let mut face_cards_map = HashMap::<char, Card>::new();
-        face_cards_map.insert('A', Card::Ace);
-        face_cards_map.insert('K', Card::King);
-        face_cards_map.insert('Q', Card::Queen);
-        face_cards_map.insert('J', Card::Jack);
-        face_cards_map.insert('T', Card::Ten);
-        face_cards_map
-    }
+    pub const COUNT: usize = 13;
 
-    fn number_cards_map() -> HashMap<char, Card> {
-        let mut number_cards_map = HashMap::<char, Card>::new();
-        number_cards_map.insert('9', Card::Nine);
-        number_cards_map.insert('8', Card::Eight);
-        number_cards_map.insert('7', Card::Seven);
-        number_cards_map.insert('6', Card::Six);
-        number_cards_map.insert('5', Card::Five);
-        number_cards_map.insert('4', Card::Four);
-        number_cards_map.insert('3', Card::Three);
-        number_cards_map.insert('2', Card::Two);
-        number_cards_map
+    /// Every card, strongest first under the standard rules.
+    pub const ALL: [Card; Card::COUNT] = [
+        Card::Ace,
+        Card::King,
+        Card::Queen,
+        Card::Jack,
+        Card::Ten,
+        Card::Nine,
+        Card::Eight,
+        Card::Seven,
+        Card::Six,
+        Card::Five,
+        Card::Four,
+        Card::Three,
+        Card::Two,
+    ];
+
+    /// Position of the card in [`Card::ALL`].
+    pub fn index(self) -> usize {
+        Card::Ace as usize - self as usize
     }
 }
 
 impl TryFrom<char> for Card {
     type Error = String;
     fn try_from(value: char) -> Result<Self, Self::Error> {
-        if let Some(card) = Self::face_cards_map().get(&value) {
-            return Ok(card.clone());
-        } else if let Some(card) = Self::number_cards_map().get(&value) {
-            return Ok(card.clone());
+        Ruleset::STANDARD.card(value)
+    }
+}
+
+impl FromStr for Card {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut symbols = s.chars();
+        match (symbols.next(), symbols.next()) {
+            (Some(symbol), None) => Card::try_from(symbol),
+            _ => Err(format!("Card can not be created from '{}'", s)),
         }
-        Err(format!("Card can not be created from char {}", value))
+    }
+}
+
+/// Writes the card's standard symbol.
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Ruleset::STANDARD.symbol(*self))
+    }
+}
+
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
     }
 }
 
@@ -89,4 +125,29 @@ mod a_card {
     fn returns_error_for_invalid_char(#[case] value: char) {
         assert!(Card::try_from(value).is_err())
     }
+
+    #[test]
+    fn can_be_indexed() {
+        for (index, card) in Card::ALL.iter().enumerate() {
+            assert_eq!(index, card.index());
+        }
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("AK")]
+    #[case("X")]
+    fn can_not_be_parsed_from_other_strings(#[case] value: &str) {
+        assert!(value.parse::<Card>().is_err());
+    }
+
+    #[test]
+    fn round_trips_through_strings_and_json() {
+        for card in Card::ALL {
+            assert_eq!(Ok(card), card.to_string().parse());
+            let json = serde_json::to_string(&card).unwrap();
+            assert_eq!(format!("\"{}\"", card), json);
+            assert_eq!(card, serde_json::from_str::<Card>(&json).unwrap());
+        }
+    }
 }
@@ -0,0 +1,107 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::Card;
+use crate::ruleset::Ruleset;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Suit {
+    Spades,
+    Hearts,
+    Diamonds,
+    Clubs,
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+    pub fn symbol(self) -> char {
+        match self {
+            Suit::Spades => 'S',
+            Suit::Hearts => 'H',
+            Suit::Diamonds => 'D',
+            Suit::Clubs => 'C',
+        }
+    }
+}
+
+impl TryFrom<char> for Suit {
+    type Error = String;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match Suit::ALL.into_iter().find(|suit| suit.symbol() == value) {
+            Some(suit) => Ok(suit),
+            None => Err(format!("Suit can not be created from char {}", value)),
+        }
+    }
+}
+
+/// A card with a suit, written as its rank then its suit, such as `AS` or `TD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SuitedCard {
+    pub card: Card,
+    pub suit: Suit,
+}
+
+impl SuitedCard {
+    pub fn new(card: Card, suit: Suit) -> Self {
+        SuitedCard { card, suit }
+    }
+}
+
+impl FromStr for SuitedCard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(card), Some(suit), None) => Ok(SuitedCard::new(
+                Ruleset::STANDARD.card(card)?,
+                Suit::try_from(suit)?,
+            )),
+            _ => Err(format!(
+                "Suited card '{}' is not a rank followed by a suit",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SuitedCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            Ruleset::STANDARD.symbol(self.card),
+            self.suit.symbol()
+        )
+    }
+}
+
+#[cfg(test)]
+mod a_suited_card {
+    use rstest::rstest;
+
+    use super::{Suit, SuitedCard};
+    use crate::card::Card;
+
+    #[rstest]
+    #[case("AS", Card::Ace, Suit::Spades)]
+    #[case("TD", Card::Ten, Suit::Diamonds)]
+    #[case("2H", Card::Two, Suit::Hearts)]
+    #[case("JC", Card::Jack, Suit::Clubs)]
+    fn can_be_parsed(#[case] value: &str, #[case] card: Card, #[case] suit: Suit) {
+        let suited_card: SuitedCard = value.parse().unwrap();
+        assert_eq!(SuitedCard::new(card, suit), suited_card);
+        assert_eq!(value, suited_card.to_string());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("A")]
+    #[case("AX")]
+    #[case("1S")]
+    #[case("ASD")]
+    fn returns_error_for_invalid_strings(#[case] value: &str) {
+        assert!(value.parse::<SuitedCard>().is_err());
+    }
+}
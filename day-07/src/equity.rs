@@ -0,0 +1,543 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use rand::rngs::StdRng;
+use rand::seq::index::sample;
+use rand::{Rng, SeedableRng};
+
+use crate::card::Card;
+use crate::hand::hand_score::HandScore;
+use crate::hand::Hand;
+use crate::ruleset::Ruleset;
+
+/// The default number of completions beyond which [`distribution`] samples rather than
+/// enumerates.
+///
+/// A completion is how many of each of the 13 cards are drawn, so no hand of up to
+/// [`Hand::MAX_SIZE`] cards has more than the 50388 ways of drawing 7, however large the deck.
+/// Enumerating that many takes milliseconds, so under this default every distribution is exact,
+/// and sampling is a fallback for callers who set a lower limit, such as to bound the time
+/// taken by many distributions or to check the sampling against the enumeration.
+pub const MAX_EXACT_OUTCOMES: u64 = 1_000_000;
+
+/// How many of each card a deck holds.
+///
+/// Decks are written as `card=count` pairs separated by commas, such as `A=4,K=2,J=8`, with
+/// cards that are not listed left out of the deck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deck {
+    counts: [u32; Card::COUNT],
+}
+
+/// Chance of each category for a completed hand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    /// Probability of each category the completed hand can score.
+    pub probabilities: BTreeMap<HandScore, f64>,
+    /// Whether every completion was enumerated, rather than sampled.
+    pub exact: bool,
+}
+
+impl Deck {
+    /// The most copies of one card a parsed deck can hold.
+    pub const MAX_COUNT: u32 = 1_000_000;
+
+    pub fn new(counts: [u32; Card::COUNT]) -> Self {
+        Deck { counts }
+    }
+
+    /// A deck with the same number of every card, such as 4 for a deck of 52.
+    pub fn uniform(copies: u32) -> Self {
+        Deck::new([copies; Card::COUNT])
+    }
+
+    pub fn count(&self, card: Card) -> u32 {
+        self.counts[card.index()]
+    }
+
+    pub fn len(&self) -> u64 {
+        self.counts.iter().map(|count| *count as u64).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Deals `amount` distinct cards of the deck at random, without listing every card in it.
+    ///
+    /// Panics if the deck holds fewer than `amount` cards.
+    pub fn deal<R: Rng + ?Sized>(&self, rng: &mut R, amount: usize) -> Vec<Card> {
+        sample(rng, self.len() as usize, amount)
+            .into_iter()
+            .map(|index| self.card_at(index as u64))
+            .collect()
+    }
+
+    /// The card at `index` were the deck laid out strongest first under the standard rules.
+    fn card_at(&self, index: u64) -> Card {
+        let mut remaining = index;
+        for card in Card::ALL {
+            match remaining.checked_sub(self.count(card) as u64) {
+                Some(rest) => remaining = rest,
+                None => return card,
+            }
+        }
+        panic!(
+            "Index {} is past the end of a deck of {}",
+            index,
+            self.len()
+        )
+    }
+
+    pub fn remove(&mut self, card: Card) -> Result<(), String> {
+        match self.counts[card.index()].checked_sub(1) {
+            Some(count) => {
+                self.counts[card.index()] = count;
+                Ok(())
+            }
+            None => Err(format!(
+                "Deck has no {} left",
+                Ruleset::STANDARD.symbol(card)
+            )),
+        }
+    }
+}
+
+impl FromStr for Deck {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut deck = Deck::uniform(0);
+        for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (symbol, count) = pair
+                .split_once('=')
+                .ok_or(format!("Deck entry '{}' is not a 'card=count' pair", pair))?;
+            let mut symbols = symbol.trim().chars();
+            let card = match (symbols.next(), symbols.next()) {
+                (Some(symbol), None) => Card::try_from(symbol)?,
+                _ => return Err(format!("Deck entry '{}' does not name one card", pair)),
+            };
+            let count = count
+                .trim()
+                .parse::<u32>()
+                .map_err(|err| format!("Failed to parse count in '{}', {}", pair, err))?;
+            if count > Deck::MAX_COUNT {
+                return Err(format!(
+                    "Deck entry '{}' holds more than {} cards",
+                    pair,
+                    Deck::MAX_COUNT
+                ));
+            }
+            deck.counts[card.index()] = count;
+        }
+        Ok(deck)
+    }
+}
+
+impl Distribution {
+    pub fn probability(&self, score: HandScore) -> f64 {
+        self.probabilities.get(&score).copied().unwrap_or(0.0)
+    }
+}
+
+/// The distribution of the category a hand of `hand_size` cards scores, once the known cards are
+/// completed from the deck. The known cards are taken out of the deck first.
+///
+/// Enumerates every completion when there are at most `max_exact_outcomes` of them, as counted
+/// by [`no_of_outcomes`], and otherwise deals `samples` seeded random completions. See
+/// [`MAX_EXACT_OUTCOMES`] for when that happens.
+pub fn distribution(
+    known: &[Card],
+    hand_size: usize,
+    deck: &Deck,
+    ruleset: &Ruleset,
+    max_exact_outcomes: u64,
+    samples: usize,
+    seed: u64,
+) -> Result<Distribution, String> {
+    match no_of_outcomes(known, hand_size, deck)? <= max_exact_outcomes {
+        true => exact_distribution(known, hand_size, deck, ruleset),
+        false => simulate_distribution(known, hand_size, deck, ruleset, samples, seed),
+    }
+}
+
+/// [`distribution`] for a partial hand, such as `KK` completed to a hand of five, with the
+/// hand's cards taken out of the deck.
+pub fn hand_distribution(
+    hand: &Hand,
+    hand_size: usize,
+    deck: &Deck,
+    ruleset: &Ruleset,
+    max_exact_outcomes: u64,
+    samples: usize,
+    seed: u64,
+) -> Result<Distribution, String> {
+    distribution(
+        hand.cards(),
+        hand_size,
+        deck,
+        ruleset,
+        max_exact_outcomes,
+        samples,
+        seed,
+    )
+}
+
+/// How many distinct completions [`exact_distribution`] enumerates, which is the number of ways
+/// of choosing how many of each card to draw from what is left of the deck.
+pub fn no_of_outcomes(known: &[Card], hand_size: usize, deck: &Deck) -> Result<u64, String> {
+    let no_of_draws = check_hand_size(known, hand_size)?;
+    let deck = remaining_deck(known, deck, no_of_draws)?;
+    // Ways of drawing each number of cards from the cards counted so far
+    let mut ways = vec![0_u64; no_of_draws + 1];
+    ways[0] = 1;
+    for card in Card::ALL {
+        let limit = deck.count(card) as usize;
+        for drawn in (0..=no_of_draws).rev() {
+            ways[drawn] = (1..=limit.min(drawn))
+                .fold(ways[drawn], |total, count| total + ways[drawn - count]);
+        }
+    }
+    Ok(ways[no_of_draws])
+}
+
+/// Enumerates every way of drawing the missing cards, weighted by how likely each is.
+pub fn exact_distribution(
+    known: &[Card],
+    hand_size: usize,
+    deck: &Deck,
+    ruleset: &Ruleset,
+) -> Result<Distribution, String> {
+    let no_of_draws = check_hand_size(known, hand_size)?;
+    let deck = remaining_deck(known, deck, no_of_draws)?;
+
+    // Draws are enumerated as how many of each card are drawn, each with the number of ways
+    // of drawing them from the deck
+    let mut probabilities = BTreeMap::new();
+    let total_ways = binomial(deck.len(), no_of_draws as u64);
+    let mut drawn = [0_u32; Card::COUNT];
+    let mut cards = known.to_vec();
+    enumerate_draws(&deck, 0, no_of_draws as u32, &mut drawn, &mut |drawn| {
+        let ways: f64 = Card::ALL
+            .iter()
+            .map(|card| binomial(deck.count(*card) as u64, drawn[card.index()] as u64))
+            .product();
+        cards.truncate(known.len());
+        for card in Card::ALL {
+            cards.extend((0..drawn[card.index()]).map(|_| card));
+        }
+        let score = HandScore::with_ruleset(&cards, ruleset);
+        *probabilities.entry(score).or_insert(0.0) += ways / total_ways;
+    });
+    Ok(Distribution {
+        probabilities,
+        exact: true,
+    })
+}
+
+/// Deals `samples` random completions from the deck, seeded so runs can be repeated.
+pub fn simulate_distribution(
+    known: &[Card],
+    hand_size: usize,
+    deck: &Deck,
+    ruleset: &Ruleset,
+    samples: usize,
+    seed: u64,
+) -> Result<Distribution, String> {
+    let no_of_draws = check_hand_size(known, hand_size)?;
+    let deck = remaining_deck(known, deck, no_of_draws)?;
+    if samples == 0 {
+        return Err("Can not simulate without any samples".to_string());
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut score_counts = BTreeMap::new();
+    let mut cards = known.to_vec();
+    for _ in 0..samples {
+        cards.truncate(known.len());
+        cards.extend(deck.deal(&mut rng, no_of_draws));
+        *score_counts
+            .entry(HandScore::with_ruleset(&cards, ruleset))
+            .or_insert(0_usize) += 1;
+    }
+    Ok(Distribution {
+        probabilities: score_counts
+            .into_iter()
+            .map(|(score, count)| (score, count as f64 / samples as f64))
+            .collect(),
+        exact: false,
+    })
+}
+
+/// Number of cards still to draw.
+fn check_hand_size(known: &[Card], hand_size: usize) -> Result<usize, String> {
+    if hand_size == 0 || hand_size > Hand::MAX_SIZE {
+        return Err(format!(
+            "Hand size {} is not between 1 and {}",
+            hand_size,
+            Hand::MAX_SIZE
+        ));
+    }
+    match hand_size.checked_sub(known.len()) {
+        Some(no_of_draws) => Ok(no_of_draws),
+        None => Err(format!(
+            "{} known cards do not fit in a hand of {}",
+            known.len(),
+            hand_size
+        )),
+    }
+}
+
+fn remaining_deck(known: &[Card], deck: &Deck, no_of_draws: usize) -> Result<Deck, String> {
+    let mut deck = *deck;
+    for card in known {
+        deck.remove(*card)?;
+    }
+    if deck.len() < no_of_draws as u64 {
+        return Err(format!(
+            "Deck has {} cards left, {} are needed",
+            deck.len(),
+            no_of_draws
+        ));
+    }
+    Ok(deck)
+}
+
+fn enumerate_draws(
+    deck: &Deck,
+    index: usize,
+    remaining: u32,
+    drawn: &mut [u32; Card::COUNT],
+    visit: &mut impl FnMut(&[u32; Card::COUNT]),
+) {
+    if index == Card::COUNT {
+        if remaining == 0 {
+            visit(drawn);
+        }
+        return;
+    }
+    for count in 0..=remaining.min(deck.counts[index]) {
+        drawn[index] = count;
+        enumerate_draws(deck, index + 1, remaining - count, drawn, visit);
+    }
+    drawn[index] = 0;
+}
+
+fn binomial(n: u64, k: u64) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    let k = k.min(n - k);
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+#[cfg(test)]
+mod a_distribution {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn cards(value: &str) -> Vec<Card> {
+        value.chars().map(|c| Card::try_from(c).unwrap()).collect()
+    }
+
+    fn assert_close(expected: f64, actual: f64, tolerance: f64) {
+        assert!(
+            (expected - actual).abs() < tolerance,
+            "{} vs {}",
+            expected,
+            actual
+        );
+    }
+
+    #[rstest]
+    #[case("32T3K", 5, HandScore::OnePair)]
+    #[case("AAAA", 5, HandScore::FourOfAKind)]
+    #[case("QQQQ", 4, HandScore::FourOfAKind)]
+    fn is_certain_when_the_outcome_is(
+        #[case] known: &str,
+        #[case] hand_size: usize,
+        #[case] expected: HandScore,
+    ) {
+        let distribution = exact_distribution(
+            &cards(known),
+            hand_size,
+            &Deck::uniform(4),
+            &Ruleset::STANDARD,
+        )
+        .unwrap();
+        assert_eq!(1, distribution.probabilities.len());
+        assert_close(1.0, distribution.probability(expected), 1e-12);
+    }
+
+    #[test]
+    fn can_complete_a_hand_under_standard_rules() {
+        // Two Aces are left in the 50 cards
+        let distribution =
+            exact_distribution(&cards("AA"), 3, &Deck::uniform(4), &Ruleset::STANDARD).unwrap();
+        assert_close(
+            0.04,
+            distribution.probability(HandScore::ThreeOfAKind),
+            1e-12,
+        );
+        assert_close(0.96, distribution.probability(HandScore::OnePair), 1e-12);
+    }
+
+    #[test]
+    fn can_complete_a_hand_under_joker_rules() {
+        // Three Aces and three Jokers are left in the 50 cards, anything else makes a pair
+        let distribution =
+            exact_distribution(&cards("AJ"), 3, &Deck::uniform(4), &Ruleset::JOKER).unwrap();
+        assert_close(
+            0.12,
+            distribution.probability(HandScore::ThreeOfAKind),
+            1e-12,
+        );
+        assert_close(0.88, distribution.probability(HandScore::OnePair), 1e-12);
+    }
+
+    #[rstest]
+    #[case("", 5, Ruleset::STANDARD)]
+    #[case("K", 5, Ruleset::JOKER)]
+    #[case("23", 7, Ruleset::STANDARD)]
+    fn sums_to_one(#[case] known: &str, #[case] hand_size: usize, #[case] ruleset: Ruleset) {
+        let distribution =
+            exact_distribution(&cards(known), hand_size, &Deck::uniform(4), &ruleset).unwrap();
+        assert_close(1.0, distribution.probabilities.values().sum(), 1e-9);
+    }
+
+    #[test]
+    fn is_confirmed_by_simulation() {
+        let deck: Deck = "A=4,K=4,Q=4,J=4,T=4,9=2".parse().unwrap();
+        for ruleset in [Ruleset::STANDARD, Ruleset::JOKER] {
+            let exact = exact_distribution(&cards("K"), 5, &deck, &ruleset).unwrap();
+            let simulated =
+                simulate_distribution(&cards("K"), 5, &deck, &ruleset, 20_000, 7).unwrap();
+            assert!(exact.exact);
+            assert!(!simulated.exact);
+            for (score, p) in exact.probabilities.iter() {
+                assert_close(*p, simulated.probability(*score), 0.015);
+            }
+        }
+    }
+
+    #[rstest]
+    #[case("", 7, "A=4,K=4,Q=4,J=4,T=4,9=4,8=4,7=4,6=4,5=4,4=4,3=4,2=4", 49205)]
+    #[case("KK", 5, "A=4,K=4,Q=4,J=4,T=4,9=4,8=4,7=4,6=4,5=4,4=4,3=4,2=4", 454)]
+    #[case("", 2, "A=1,K=1", 1)]
+    #[case("", 3, "A=2,K=2", 2)]
+    #[case("AK", 2, "A=1,K=1", 1)]
+    #[case("", 7, "A=1000000,K=1000000,Q=1000000,J=1000000,T=1000000,9=1000000,8=1000000,7=1000000,6=1000000,5=1000000,4=1000000,3=1000000,2=1000000", 50388)]
+    fn can_count_its_outcomes(
+        #[case] known: &str,
+        #[case] hand_size: usize,
+        #[case] deck: &str,
+        #[case] expected: u64,
+    ) {
+        let deck: Deck = deck.parse().unwrap();
+        assert_eq!(
+            Ok(expected),
+            no_of_outcomes(&cards(known), hand_size, &deck)
+        );
+    }
+
+    #[test]
+    fn enumerates_when_feasible() {
+        let distribution = distribution(
+            &cards("KK"),
+            5,
+            &Deck::uniform(4),
+            &Ruleset::STANDARD,
+            MAX_EXACT_OUTCOMES,
+            100,
+            1,
+        )
+        .unwrap();
+        assert!(distribution.exact);
+    }
+
+    #[rstest]
+    #[case(453, false)]
+    #[case(454, true)]
+    fn samples_beyond_its_limit(#[case] max_exact_outcomes: u64, #[case] exact: bool) {
+        let distribution = distribution(
+            &cards("KK"),
+            5,
+            &Deck::uniform(4),
+            &Ruleset::STANDARD,
+            max_exact_outcomes,
+            10_000,
+            1,
+        )
+        .unwrap();
+        assert_eq!(exact, distribution.exact);
+        assert_close(1.0, distribution.probabilities.values().sum(), 1e-9);
+    }
+
+    #[rstest]
+    #[case("KK", 5, Ruleset::STANDARD)]
+    #[case("JJ2", 7, Ruleset::JOKER)]
+    #[case("AKQJT98", 7, Ruleset::JOKER)]
+    fn can_complete_a_partial_hand(
+        #[case] known: &str,
+        #[case] hand_size: usize,
+        #[case] ruleset: Ruleset,
+    ) {
+        let hand = Hand::parse(known, &ruleset).unwrap();
+        let deck = Deck::uniform(4);
+        assert_eq!(
+            distribution(
+                &cards(known),
+                hand_size,
+                &deck,
+                &ruleset,
+                MAX_EXACT_OUTCOMES,
+                1,
+                0
+            ),
+            hand_distribution(&hand, hand_size, &deck, &ruleset, MAX_EXACT_OUTCOMES, 1, 0)
+        );
+    }
+
+    #[rstest]
+    #[case("AAAAA", 5, "A=4")]
+    #[case("KK", 3, "K=1")]
+    #[case("KK", 5, "K=3,Q=1")]
+    #[case("KKQ", 2, "K=4")]
+    #[case("", 8, "K=8")]
+    fn rejects_impossible_hands(#[case] known: &str, #[case] hand_size: usize, #[case] deck: &str) {
+        let deck: Deck = deck.parse().unwrap();
+        assert!(exact_distribution(&cards(known), hand_size, &deck, &Ruleset::STANDARD).is_err());
+    }
+
+    #[rstest]
+    #[case("A4")]
+    #[case("AK=4")]
+    #[case("X=4")]
+    #[case("A=x")]
+    #[case("A=1000001")]
+    #[case("A=4000000000")]
+    fn rejects_invalid_decks(#[case] deck: &str) {
+        assert!(deck.parse::<Deck>().is_err());
+    }
+
+    #[test]
+    fn counts_decks_too_large_for_a_u32() {
+        assert_eq!(13 * u32::MAX as u64, Deck::uniform(u32::MAX).len());
+    }
+
+    #[test]
+    fn deals_distinct_cards_of_a_large_deck() {
+        let deck: Deck = "A=1000000,K=1000000,2=1".parse().unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+        let cards = deck.deal(&mut rng, 7);
+        assert_eq!(7, cards.len());
+        assert!(cards
+            .iter()
+            .all(|card| [Card::Ace, Card::King, Card::Two].contains(card)));
+        let deck: Deck = "Q=2,2=1".parse().unwrap();
+        let cards = deck.deal(&mut rng, 3);
+        assert_eq!(2, cards.iter().filter(|card| **card == Card::Queen).count());
+        assert_eq!(1, cards.iter().filter(|card| **card == Card::Two).count());
+    }
+}
@@ -1,22 +1,138 @@
 pub mod hand_score;
+pub mod poker;
+pub mod substitution;
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
 
 use super::card::Card;
+use crate::ruleset::Ruleset;
 use hand_score::HandScore;
+use substitution::Substitution;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Hands are serialized with the rank of each card and the wildcards among them, so that a hand
+/// dealt under any ruleset reads back the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "HandData", try_from = "HandData")]
 pub struct Hand {
-    cards: [Card; 5],
+    /// The hand's cards, followed by unused slots which are always `Card::Two`.
+    cards: [Card; Hand::MAX_SIZE],
+    len: u8,
+    /// A bit per position, set where the card was wild under the hand's ruleset.
+    wild: u8,
     score: HandScore,
+    /// The score in the top bits, then the rank of each card under the ruleset the hand was
+    /// dealt with, four bits each. Unused slots rank 0, so a hand and the same hand with more
+    /// cards can share a key, which ordering then settles by size.
+    key: u32,
 }
 
 impl Hand {
-    pub fn new(cards: [Card; 5]) -> Self {
-        let score = HandScore::from(cards);
-        Hand { score, cards }
+    /// The largest hand whose sort key fits in a `u32`.
+    pub const MAX_SIZE: usize = 7;
+
+    pub fn new<const N: usize>(cards: [Card; N]) -> Self {
+        Hand::with_ruleset(cards, &Ruleset::STANDARD)
+    }
+
+    pub fn with_ruleset<const N: usize>(cards: [Card; N], ruleset: &Ruleset) -> Self {
+        const { assert!(N > 0 && N <= Hand::MAX_SIZE, "Hands hold 1 to 7 cards") };
+        Hand::from_slice(&cards, ruleset).expect("Hand size is checked at compile time")
+    }
+
+    /// Builds a hand of any size from 1 to [`Hand::MAX_SIZE`] cards.
+    pub fn from_slice(cards: &[Card], ruleset: &Ruleset) -> Result<Self, String> {
+        if cards.is_empty() || cards.len() > Hand::MAX_SIZE {
+            return Err(format!(
+                "Hand of {} cards is not between 1 and {} cards",
+                cards.len(),
+                Hand::MAX_SIZE
+            ));
+        }
+        let score = HandScore::with_ruleset(cards, ruleset);
+        let mut key = (score as u32) << (4 * Hand::MAX_SIZE);
+        let mut stored = [Card::Two; Hand::MAX_SIZE];
+        let mut wild = 0;
+        for (i, card) in cards.iter().enumerate() {
+            stored[i] = *card;
+            key |= (ruleset.rank(*card) as u32) << (4 * (Hand::MAX_SIZE - 1 - i));
+            if ruleset.is_wildcard(*card) {
+                wild |= 1 << i;
+            }
+        }
+        Ok(Hand {
+            cards: stored,
+            len: cards.len() as u8,
+            wild,
+            score,
+            key,
+        })
+    }
+
+    /// Reads a hand with one character per card, its size taken from the number of characters.
+    pub fn parse(value: &str, ruleset: &Ruleset) -> Result<Self, String> {
+        let cards = value
+            .chars()
+            .map(|symbol| ruleset.card(symbol))
+            .collect::<Result<Vec<Card>, String>>()?;
+        Hand::from_slice(&cards, ruleset)
+    }
+
+    /// The hand written with the ruleset's alphabet, one character per card.
+    pub fn to_symbols(&self, ruleset: &Ruleset) -> String {
+        self.cards()
+            .iter()
+            .map(|card| ruleset.symbol(*card))
+            .collect()
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards[..self.len as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn score(&self) -> HandScore {
+        self.score
     }
 
-    pub fn cards(&self) -> &[Card; 5] {
-        &self.cards
+    pub fn sort_key(&self) -> u32 {
+        self.key
+    }
+
+    /// The rank of each card under the ruleset the hand was dealt with.
+    pub fn ranks(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..self.len()).map(|i| (self.key >> (4 * (Hand::MAX_SIZE - 1 - i)) & 0xF) as u8)
+    }
+
+    /// Whether the card at `position` was wild under the ruleset the hand was dealt with.
+    pub fn is_wild(&self, position: usize) -> bool {
+        position < self.len() && self.wild & (1 << position) != 0
+    }
+
+    /// Which card each of the hand's wildcards under `ruleset` stands in for.
+    pub fn best_substitution(&self, ruleset: &Ruleset) -> Substitution {
+        Substitution::best(self, ruleset)
+    }
+
+    /// The position, from 0, of the first card that ranks differently from the other hand's,
+    /// when both hands score the same. `None` if the scores differ, the hands tie, or the cards
+    /// of the shorter hand all rank the same as the longer hand's first cards.
+    pub fn deciding_position(&self, other: &Hand) -> Option<usize> {
+        if self.score != other.score {
+            return None;
+        }
+        self.ranks()
+            .zip(other.ranks())
+            .position(|(rank, other_rank)| rank != other_rank)
     }
 }
 
@@ -26,38 +142,294 @@ impl PartialOrd for Hand {
     }
 }
 
+/// Orders hands by their sort key, then shorter hands first. Hands dealt under different rulesets
+/// can share a key with different cards or wildcards, so those fall back to comparing both to
+/// stay consistent with `==`.
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match self.score.cmp(&other.score) {
-            core::cmp::Ordering::Equal => {}
-            ord => return ord,
-        }
-        for card_pair in self.cards.iter().zip(other.cards.iter()) {
-            match card_pair.0.cmp(card_pair.1) {
-                core::cmp::Ordering::Equal => {}
-                ord => return ord,
+        self.key
+            .cmp(&other.key)
+            .then(self.len.cmp(&other.len))
+            .then_with(|| self.cards.cmp(&other.cards))
+            .then(self.wild.cmp(&other.wild))
+    }
+}
+
+impl<const N: usize> TryFrom<[char; N]> for Hand {
+    type Error = String;
+    fn try_from(value: [char; N]) -> Result<Self, Self::Error> {
+        Hand::parse(&value.iter().collect::<String>(), &Ruleset::STANDARD)
+    }
+}
+
+impl TryFrom<&str> for Hand {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Hand::parse(value, &Ruleset::STANDARD)
+    }
+}
+
+/// Reads a hand under the standard rules. Hands dealt under other rulesets are read with
+/// [`Hand::parse`].
+impl FromStr for Hand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Hand::parse(s, &Ruleset::STANDARD)
+    }
+}
+
+/// Writes the hand with the standard alphabet, whichever ruleset it was dealt with.
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.cards()
+            .iter()
+            .try_for_each(|card| write!(f, "{}", card))
+    }
+}
+
+/// A hand as it is serialized, such as `{"cards":"KTJJT","ranks":[11,9,0,0,9],"wildcards":"J"}`
+/// for a hand under the Joker rules.
+#[derive(Serialize, Deserialize)]
+struct HandData {
+    /// In the standard alphabet.
+    cards: String,
+    ranks: Vec<u8>,
+    wildcards: String,
+}
+
+impl From<Hand> for HandData {
+    fn from(hand: Hand) -> Self {
+        let mut wildcards = String::new();
+        for (position, card) in hand.cards().iter().enumerate() {
+            let symbol = Ruleset::STANDARD.symbol(*card);
+            if hand.is_wild(position) && !wildcards.contains(symbol) {
+                wildcards.push(symbol);
             }
         }
-        core::cmp::Ordering::Equal
+        HandData {
+            cards: hand.to_string(),
+            ranks: hand.ranks().collect(),
+            wildcards,
+        }
     }
 }
 
-impl TryFrom<[char; 5]> for Hand {
+impl TryFrom<HandData> for Hand {
     type Error = String;
-    fn try_from(value: [char; 5]) -> Result<Self, Self::Error> {
-        let cards: Vec<Card> = match value.into_iter().map(|val| Card::try_from(val)).collect() {
-            Ok(card) => card,
-            Err(err) => return Err(err),
+
+    fn try_from(data: HandData) -> Result<Self, Self::Error> {
+        let cards = data
+            .cards
+            .chars()
+            .map(Card::try_from)
+            .collect::<Result<Vec<Card>, String>>()?;
+        if cards.len() != data.ranks.len() {
+            return Err(format!(
+                "Hand '{}' has {} ranks, expected one per card",
+                data.cards,
+                data.ranks.len()
+            ));
+        }
+        let wildcards = data
+            .wildcards
+            .chars()
+            .map(Card::try_from)
+            .collect::<Result<Vec<Card>, String>>()?;
+        let ranks: Vec<(Card, u8)> = cards.iter().copied().zip(data.ranks).collect();
+        let ruleset = Ruleset::completing(&ranks, &wildcards)?;
+        Hand::from_slice(&cards, &ruleset)
+    }
+}
+
+#[cfg(test)]
+mod a_hand {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rstest::rstest;
+
+    use super::hand_score::HandScore;
+    use super::Hand;
+    use crate::card::Card;
+    use crate::ruleset::Ruleset;
+
+    fn hand(value: &str, ruleset: &Ruleset) -> Hand {
+        Hand::parse(value, ruleset).unwrap()
+    }
+
+    #[rstest]
+    #[case("32T3K", HandScore::OnePair, HandScore::OnePair)]
+    #[case("T55J5", HandScore::ThreeOfAKind, HandScore::FourOfAKind)]
+    #[case("KTJJT", HandScore::TwoPair, HandScore::FourOfAKind)]
+    #[case("QQQJA", HandScore::ThreeOfAKind, HandScore::FourOfAKind)]
+    #[case("JJJJJ", HandScore::FiveOfAKind, HandScore::FiveOfAKind)]
+    #[case("2345J", HandScore::HighCard, HandScore::OnePair)]
+    #[case("2J4J6", HandScore::OnePair, HandScore::ThreeOfAKind)]
+    #[case("22J33", HandScore::TwoPair, HandScore::FullHouse)]
+    fn is_scored_by_its_ruleset(
+        #[case] value: &str,
+        #[case] standard: HandScore,
+        #[case] joker: HandScore,
+    ) {
+        assert_eq!(standard, hand(value, &Ruleset::STANDARD).score());
+        assert_eq!(joker, hand(value, &Ruleset::JOKER).score());
+    }
+
+    #[rstest]
+    #[case("KTJJT", "KK677", Ruleset::STANDARD)]
+    #[case("KK677", "KTJJT", Ruleset::JOKER)]
+    #[case("JKKK2", "QQQQ2", Ruleset::STANDARD)]
+    #[case("JKKK2", "QQQQ2", Ruleset::JOKER)]
+    #[case("T55J5", "QQQJA", Ruleset::JOKER)]
+    fn is_ordered_by_its_ruleset(
+        #[case] weaker: &str,
+        #[case] stronger: &str,
+        #[case] ruleset: Ruleset,
+    ) {
+        assert!(hand(weaker, &ruleset) < hand(stronger, &ruleset));
+    }
+
+    #[test]
+    fn can_be_ordered_by_a_user_defined_ruleset() {
+        // Twos high, with no wildcards
+        let ruleset: Ruleset = "order: 2AKQJT9876543".parse().unwrap();
+        assert!(hand("AAKKQ", &ruleset) < hand("22KKQ", &ruleset));
+        assert!(hand("AAKKQ", &Ruleset::STANDARD) > hand("22KKQ", &Ruleset::STANDARD));
+    }
+
+    #[rstest]
+    #[case("33332", "2AAAA", Some(0))]
+    #[case("KK677", "KTJJT", Some(1))]
+    #[case("T55J5", "QQQJA", Some(0))]
+    #[case("KK677", "KK676", Some(4))]
+    #[case("KK677", "QQQJA", None)]
+    #[case("KK677", "KK677", None)]
+    #[case("KKA", "KKA2", None)]
+    #[case("KKA", "KK2A", Some(2))]
+    #[case("KK2", "KK2AA", None)]
+    fn can_find_the_deciding_position(
+        #[case] value: &str,
+        #[case] other: &str,
+        #[case] expected: Option<usize>,
+    ) {
+        let (value, other) = (
+            hand(value, &Ruleset::STANDARD),
+            hand(other, &Ruleset::STANDARD),
+        );
+        assert_eq!(expected, value.deciding_position(&other));
+        assert_eq!(expected, other.deciding_position(&value));
+    }
+
+    #[rstest]
+    #[case("KKA", "KKA2")]
+    #[case("KKA", "KKA22")]
+    #[case("2", "22")]
+    fn orders_a_shorter_hand_sharing_a_key_first(#[case] shorter: &str, #[case] longer: &str) {
+        let (shorter, longer) = (
+            hand(shorter, &Ruleset::STANDARD),
+            hand(longer, &Ruleset::STANDARD),
+        );
+        assert_ne!(shorter, longer);
+        assert!(shorter < longer);
+    }
+
+    #[test]
+    fn can_share_a_sort_key_with_a_longer_hand() {
+        assert_eq!(
+            hand("KKA", &Ruleset::STANDARD).sort_key(),
+            hand("KKA2", &Ruleset::STANDARD).sort_key()
+        );
+    }
+
+    #[test]
+    fn is_equal_only_to_an_identical_hand() {
+        let mut rng = StdRng::seed_from_u64(43);
+        let random_hand = |rng: &mut StdRng| {
+            let cards: Vec<Card> = (0..rng.gen_range(1..=3))
+                .map(|_| Card::ALL[rng.gen_range(9..Card::COUNT)])
+                .collect();
+            Hand::from_slice(&cards, &Ruleset::STANDARD).unwrap()
         };
-        let cards: [Card; 5] = match cards.try_into() {
-            Ok(cards) => cards,
-            Err(err) => {
-                return Err(format!(
-                    "Failed converting vector of cards {:?} into card array",
-                    err
-                ))
+        for _ in 0..10_000 {
+            let (a, b) = (random_hand(&mut rng), random_hand(&mut rng));
+            assert_eq!(
+                a == b,
+                a.cmp(&b) == std::cmp::Ordering::Equal,
+                "{} {}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn can_be_packed_into_a_sort_key() {
+        // One pair, then the ranks of 3, 2, T, 3 and K and two unused slots
+        assert_eq!(0x2108_1B00, hand("32T3K", &Ruleset::STANDARD).sort_key());
+        assert_eq!(0x7AAA_AA00, hand("QQQQQ", &Ruleset::STANDARD).sort_key());
+    }
+
+    #[test]
+    fn can_be_written_with_a_ruleset_alphabet() {
+        let ruleset: Ruleset = "alphabet: AKQWT98765432".parse().unwrap();
+        let hand = hand("KTWWT", &ruleset);
+        assert_eq!("KTWWT", hand.to_symbols(&ruleset));
+        assert_eq!("KTJJT", hand.to_string());
+    }
+
+    #[rstest]
+    #[case(Ruleset::STANDARD)]
+    #[case(Ruleset::JOKER)]
+    #[case("order: 2AKQJT9876543\nwildcards: 7 2".parse().unwrap())]
+    fn round_trips_through_strings_and_json(#[case] ruleset: Ruleset) {
+        let mut rng = StdRng::seed_from_u64(48);
+        for _ in 0..1000 {
+            let cards: Vec<Card> = (0..rng.gen_range(1..=Hand::MAX_SIZE))
+                .map(|_| Card::ALL[rng.gen_range(0..Card::COUNT)])
+                .collect();
+            let hand = Hand::from_slice(&cards, &ruleset).unwrap();
+            let written = hand.to_symbols(&ruleset);
+            assert_eq!(cards.len(), written.chars().count());
+            assert_eq!(Ok(hand), Hand::parse(&written, &ruleset));
+            if ruleset == Ruleset::STANDARD {
+                assert_eq!(Ok(hand), hand.to_string().parse());
             }
-        };
-        Ok(Hand::new(cards))
+            let json = serde_json::to_string(&hand).unwrap();
+            assert_eq!(
+                hand,
+                serde_json::from_str::<Hand>(&json).unwrap(),
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn is_serialized_with_its_ranks_and_wildcards() {
+        let joker = hand("KTJJT", &Ruleset::JOKER);
+        let json = serde_json::to_string(&joker).unwrap();
+        assert_eq!(
+            r#"{"cards":"KTJJT","ranks":[11,9,0,0,9],"wildcards":"J"}"#,
+            json
+        );
+        let read: Hand = serde_json::from_str(&json).unwrap();
+        assert_eq!(joker, read);
+        assert_eq!(HandScore::FourOfAKind, read.score());
+        assert_ne!(hand("KTJJT", &Ruleset::STANDARD), read);
+    }
+
+    #[rstest]
+    #[case("\"KTJJT\"")]
+    #[case(r#"{"cards":"","ranks":[],"wildcards":""}"#)]
+    #[case(r#"{"cards":"32T3X","ranks":[1,0,8,1,11],"wildcards":""}"#)]
+    #[case(r#"{"cards":"32T3K","ranks":[1,0,8,1],"wildcards":""}"#)]
+    #[case(r#"{"cards":"32T3K","ranks":[1,0,8,2,11],"wildcards":""}"#)]
+    #[case(r#"{"cards":"32T3K","ranks":[1,1,8,1,11],"wildcards":""}"#)]
+    #[case(r#"{"cards":"32T3K","ranks":[1,0,8,1,13],"wildcards":""}"#)]
+    #[case(r#"{"cards":"32T3K","ranks":[1,0,8,1,11],"wildcards":"X"}"#)]
+    #[case(r#"{"cards":"AKQJT98765","ranks":[12,11,10,9,8,7,6,5,4,3],"wildcards":""}"#)]
+    #[case("5")]
+    fn can_not_be_read_from_invalid_json(#[case] json: &str) {
+        assert!(serde_json::from_str::<Hand>(json).is_err());
     }
 }
@@ -1,11 +1,27 @@
-use std::collections::HashMap;
-use std::hash::Hash;
-use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
+#[cfg(test)]
+mod pattern;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
 
 use crate::card::Card;
+use crate::ruleset::Ruleset;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// Read and written by variant name, such as `TwoPair`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    EnumIter,
+)]
 pub enum HandScore {
     HighCard = 1,
     OnePair = 2,
@@ -14,151 +30,185 @@ pub enum HandScore {
     FullHouse = 5,
     FourOfAKind = 6,
     FiveOfAKind = 7,
+    SixOfAKind = 8,
+    SevenOfAKind = 9,
 }
 
 impl HandScore {
-    pub fn from(value: [Card; 5]) -> Self {
-        let pattern = PatternValue::get_pattern_values(value);
-        HandScore::from_pattern(pattern)
+    pub fn from(value: &[Card]) -> Self {
+        HandScore::with_ruleset(value, &Ruleset::STANDARD)
     }
 
-    fn from_pattern(pattern: Pattern) -> Self {
-        Pattern::pattern_handscore_map()
-            .get(&pattern)
-            .expect(&format!(
-                "No handscore for provided pattern {:?} found",
-                pattern
-            ))
-            .clone()
+    /// Scores a hand with each wildcard standing in for the card that makes the strongest hand.
+    pub fn with_ruleset(value: &[Card], ruleset: &Ruleset) -> Self {
+        let mut occurences = [0_u8; Card::COUNT];
+        let mut wildcards = 0;
+        for card in value.iter().copied() {
+            match ruleset.is_wildcard(card) {
+                true => wildcards += 1,
+                false => occurences[card.index()] += 1,
+            }
+        }
+        HandScore::from_counts(&occurences, wildcards)
     }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-struct Pattern([PatternValue; 5]);
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter, Hash)]
-enum PatternValue {
-    CardA,
-    CardB,
-    CardC,
-    CardD,
-    CardE,
-}
 
-impl Pattern {
-    fn pattern_handscore_map() -> HashMap<Pattern, HandScore> {
-        let five_of_a_kind_pattern = Pattern([PatternValue::CardA; 5]);
-        let four_of_a_kind_pattern = Pattern([
-            PatternValue::CardA,
-            PatternValue::CardA,
-            PatternValue::CardA,
-            PatternValue::CardA,
-            PatternValue::CardB,
-        ]);
-        let full_house_pattern = Pattern([
-            PatternValue::CardA,
-            PatternValue::CardA,
-            PatternValue::CardA,
-            PatternValue::CardB,
-            PatternValue::CardB,
-        ]);
-        let three_of_a_kind_pattern = Pattern([
-            PatternValue::CardA,
-            PatternValue::CardA,
-            PatternValue::CardA,
-            PatternValue::CardB,
-            PatternValue::CardC,
-        ]);
-        let two_pair_pattern = Pattern([
-            PatternValue::CardA,
-            PatternValue::CardA,
-            PatternValue::CardB,
-            PatternValue::CardB,
-            PatternValue::CardC,
-        ]);
-        let one_pair_pattern = Pattern([
-            PatternValue::CardA,
-            PatternValue::CardA,
-            PatternValue::CardB,
-            PatternValue::CardC,
-            PatternValue::CardD,
-        ]);
-        let high_card_pattern = Pattern([
-            PatternValue::CardA,
-            PatternValue::CardB,
-            PatternValue::CardC,
-            PatternValue::CardD,
-            PatternValue::CardE,
-        ]);
-        let mut pattern_handscore_map = HashMap::<Pattern, HandScore>::new();
-        pattern_handscore_map.insert(five_of_a_kind_pattern, HandScore::FiveOfAKind);
-        pattern_handscore_map.insert(four_of_a_kind_pattern, HandScore::FourOfAKind);
-        pattern_handscore_map.insert(full_house_pattern, HandScore::FullHouse);
-        pattern_handscore_map.insert(three_of_a_kind_pattern, HandScore::ThreeOfAKind);
-        pattern_handscore_map.insert(two_pair_pattern, HandScore::TwoPair);
-        pattern_handscore_map.insert(one_pair_pattern, HandScore::OnePair);
-        pattern_handscore_map.insert(high_card_pattern, HandScore::HighCard);
-        pattern_handscore_map
+    /// Scores a hand of any size from how often each card occurs, without allocating.
+    ///
+    /// The largest group of equal cards decides the category. A second group only matters when
+    /// it can make two pair or a full house, so that a hand's category is the best of any five of
+    /// its cards, extended with six and seven of a kind. Wildcards always do best joining the
+    /// largest group.
+    pub fn from_counts(occurences: &[u8], wildcards: u8) -> Self {
+        let (highest, second) = top_two_counts(occurences);
+        match (highest + wildcards, second) {
+            (7.., _) => HandScore::SevenOfAKind,
+            (6, _) => HandScore::SixOfAKind,
+            (5, _) => HandScore::FiveOfAKind,
+            (4, _) => HandScore::FourOfAKind,
+            (3, 2..) => HandScore::FullHouse,
+            (3, _) => HandScore::ThreeOfAKind,
+            (2, 2..) => HandScore::TwoPair,
+            (2, _) => HandScore::OnePair,
+            _ => HandScore::HighCard,
+        }
     }
 }
 
-impl Hash for Pattern {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        for pattern in self.0 {
-            pattern.hash(state);
+/// The largest and second largest of the counts.
+pub(crate) fn top_two_counts(occurences: &[u8]) -> (u8, u8) {
+    let (mut highest, mut second) = (0, 0);
+    for count in occurences.iter().copied() {
+        if count > highest {
+            second = highest;
+            highest = count;
+        } else if count > second {
+            second = count;
         }
     }
+    (highest, second)
 }
 
-impl PatternValue {
-    fn get_pattern_values(value: [Card; 5]) -> Pattern {
-        let sorted_cards = Card::sort_by_occurences(value);
-        let mut patterns = vec![];
-        let mut card_iter = sorted_cards.into_iter();
-        let mut pattern_iter = PatternValue::iter();
-        let mut current_pattern = pattern_iter.next().unwrap();
-        // Always push CardA for first value
-        patterns.push(current_pattern.clone());
-        let mut last_card = card_iter.next().expect("Expected card");
-        while let Some(card) = card_iter.next() {
-            match card == last_card {
-                true => {}
-                false => current_pattern = pattern_iter.next().unwrap(),
+#[cfg(test)]
+mod a_hand_score {
+    use rstest::rstest;
+    use strum::IntoEnumIterator;
+
+    use super::{pattern::from_pattern, HandScore};
+    use crate::card::Card;
+    use crate::ruleset::Ruleset;
+
+    /// Every hand of five cards, up to the order of its cards which scoring ignores.
+    fn every_hand() -> Vec<[Card; 5]> {
+        let mut hands = vec![];
+        for a in 0..Card::COUNT {
+            for b in a..Card::COUNT {
+                for c in b..Card::COUNT {
+                    for d in c..Card::COUNT {
+                        for e in d..Card::COUNT {
+                            hands.push([a, b, c, d, e].map(|index| Card::ALL[index]));
+                        }
+                    }
+                }
             }
-            patterns.push(current_pattern);
-            last_card = card;
         }
-        let pattern_values = patterns
-            .try_into()
-            .expect("Error creating patter value array");
-        Pattern(pattern_values)
+        hands
     }
-}
 
-impl Card {
-    fn sort_by_occurences(cards: [Card; 5]) -> [Card; 5] {
-        let mut occurences = HashMap::<Card, u8>::new();
-        for card in cards {
-            match occurences.get_key_value(&card) {
-                Some(kvp) => _ = occurences.insert(card, kvp.1 + 1),
-                None => _ = occurences.insert(card, 1),
-            }
+    #[rstest]
+    #[case([0, 0, 5], 0, HandScore::FiveOfAKind)]
+    #[case([1, 4, 0], 0, HandScore::FourOfAKind)]
+    #[case([2, 3, 0], 0, HandScore::FullHouse)]
+    #[case([1, 1, 3], 0, HandScore::ThreeOfAKind)]
+    #[case([2, 1, 2], 0, HandScore::TwoPair)]
+    #[case([1, 1, 2], 1, HandScore::ThreeOfAKind)]
+    #[case([2, 0, 2], 1, HandScore::FullHouse)]
+    #[case([0, 0, 0], 5, HandScore::FiveOfAKind)]
+    #[case([1, 1, 1], 2, HandScore::ThreeOfAKind)]
+    #[case([3, 3, 0], 0, HandScore::FullHouse)]
+    #[case([2, 2, 2], 0, HandScore::TwoPair)]
+    #[case([4, 2, 1], 0, HandScore::FourOfAKind)]
+    #[case([6, 1, 0], 0, HandScore::SixOfAKind)]
+    #[case([5, 0, 0], 2, HandScore::SevenOfAKind)]
+    fn can_be_classified_from_counts(
+        #[case] occurences: [u8; 3],
+        #[case] wildcards: u8,
+        #[case] expected: HandScore,
+    ) {
+        assert_eq!(expected, HandScore::from_counts(&occurences, wildcards));
+    }
+
+    #[rstest]
+    #[case("AKQ", HandScore::HighCard)]
+    #[case("A2A", HandScore::OnePair)]
+    #[case("222", HandScore::ThreeOfAKind)]
+    #[case("2323", HandScore::TwoPair)]
+    #[case("3332", HandScore::ThreeOfAKind)]
+    #[case("3333", HandScore::FourOfAKind)]
+    #[case("23456T", HandScore::HighCard)]
+    #[case("223344", HandScore::TwoPair)]
+    #[case("222333", HandScore::FullHouse)]
+    #[case("22222K", HandScore::FiveOfAKind)]
+    #[case("KKKKKK", HandScore::SixOfAKind)]
+    #[case("AKQJT98", HandScore::HighCard)]
+    #[case("22233TT", HandScore::FullHouse)]
+    #[case("2222333", HandScore::FourOfAKind)]
+    #[case("9999999", HandScore::SevenOfAKind)]
+    fn can_score_hands_of_any_size(#[case] value: &str, #[case] expected: HandScore) {
+        let cards: Vec<Card> = value.chars().map(|c| Card::try_from(c).unwrap()).collect();
+        assert_eq!(expected, HandScore::from(&cards));
+    }
+
+    #[test]
+    fn agrees_with_the_pattern_method_for_every_hand() {
+        let hands = every_hand();
+        assert_eq!(6188, hands.len());
+        for hand in hands {
+            assert_eq!(from_pattern(hand), HandScore::from(&hand), "{:?}", hand);
         }
-        let mut occurence_pairs: Vec<(Card, u8)> = occurences.into_iter().collect();
-        occurence_pairs.sort_by(|a, b| a.1.cmp(&b.1));
-        occurence_pairs.reverse();
-        let mut reconstructed_cards = vec![];
-        for card in occurence_pairs.into_iter() {
-            let mut occurences = card.1.clone();
-            while occurences > 0 {
-                reconstructed_cards.push(card.0);
-                occurences -= 1;
-            }
+    }
+
+    #[test]
+    fn agrees_with_the_best_substitution_for_every_joker_hand() {
+        for hand in every_hand()
+            .into_iter()
+            .filter(|hand| hand.contains(&Card::Jack))
+        {
+            let best = Card::ALL
+                .into_iter()
+                .map(|substitute| {
+                    from_pattern(hand.map(|card| match card {
+                        Card::Jack => substitute,
+                        card => card,
+                    }))
+                })
+                .max()
+                .unwrap();
+            assert_eq!(
+                best,
+                HandScore::with_ruleset(&hand, &Ruleset::JOKER),
+                "{:?}",
+                hand
+            );
         }
-        let cards: [Card; 5] = reconstructed_cards
-            .try_into()
-            .expect("Error creating new Card array while sorting by occurences");
+    }
+
+    #[test]
+    fn round_trips_through_names_and_json() {
+        assert_eq!(9, HandScore::iter().count());
+        for score in HandScore::iter() {
+            assert_eq!(Ok(score), score.to_string().parse());
+            let json = serde_json::to_string(&score).unwrap();
+            assert_eq!(format!("\"{}\"", score), json);
+            assert_eq!(score, serde_json::from_str::<HandScore>(&json).unwrap());
+        }
+        assert_eq!("FullHouse", HandScore::FullHouse.to_string());
+    }
 
-        cards
+    #[rstest]
+    #[case("Full House")]
+    #[case("fullhouse")]
+    #[case("")]
+    fn can_not_be_parsed_from_other_names(#[case] value: &str) {
+        assert!(value.parse::<HandScore>().is_err());
     }
 }
@@ -0,0 +1,242 @@
+use std::str::FromStr;
+
+use super::hand_score::top_two_counts;
+use crate::card::suited::SuitedCard;
+use crate::card::Card;
+use crate::ruleset::Ruleset;
+
+/// Categories of standard poker, where suits, straights and flushes count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PokerScore {
+    HighCard = 1,
+    OnePair = 2,
+    TwoPair = 3,
+    ThreeOfAKind = 4,
+    Straight = 5,
+    Flush = 6,
+    FullHouse = 7,
+    FourOfAKind = 8,
+    StraightFlush = 9,
+}
+
+/// Five suited cards, ordered by standard poker rules rather than Camel Cards ones.
+///
+/// Hands of the same category are compared by the ranks of their largest groups first, then
+/// by their kickers, and straights only by their highest card, with an ace-low straight
+/// topped by the Five. Hands that tie, such as ones differing only by suit, are equal.
+#[derive(Debug, Clone, Copy)]
+pub struct PokerHand {
+    cards: [SuitedCard; 5],
+    score: PokerScore,
+    /// The score in the top bits, then the tiebreak ranks plus one, four bits each, so that an
+    /// Ace playing low can rank below a Two.
+    key: u32,
+}
+
+impl PokerHand {
+    pub fn new(cards: [SuitedCard; 5]) -> Result<Self, String> {
+        for (i, card) in cards.iter().enumerate() {
+            if cards[..i].contains(card) {
+                return Err(format!("Hand has {} more than once", card));
+            }
+        }
+
+        let rank = |card: &SuitedCard| Ruleset::STANDARD.rank(card.card) as u32 + 1;
+        let mut occurences = [0_u8; Card::COUNT];
+        for card in cards.iter() {
+            occurences[card.card.index()] += 1;
+        }
+        // Ranks by the size of their group, then by rank
+        let mut tiebreak = cards.map(|card| (occurences[card.card.index()], rank(&card)));
+        tiebreak.sort_by(|a, b| b.cmp(a));
+        let mut tiebreak = tiebreak.map(|(_, rank)| rank);
+
+        let flush = cards.iter().all(|card| card.suit == cards[0].suit);
+        let distinct = tiebreak.windows(2).all(|pair| pair[0] != pair[1]);
+        let ace_low = tiebreak == [13, 4, 3, 2, 1];
+        let straight = distinct && (tiebreak[0] - tiebreak[4] == 4 || ace_low);
+        if ace_low {
+            tiebreak = [4, 3, 2, 1, 0];
+        }
+
+        let (highest, second) = top_two_counts(&occurences);
+        let score = match (straight, flush, highest, second) {
+            (true, true, _, _) => PokerScore::StraightFlush,
+            (_, _, 4, _) => PokerScore::FourOfAKind,
+            (_, _, 3, 2) => PokerScore::FullHouse,
+            (_, true, _, _) => PokerScore::Flush,
+            (true, _, _, _) => PokerScore::Straight,
+            (_, _, 3, _) => PokerScore::ThreeOfAKind,
+            (_, _, 2, 2) => PokerScore::TwoPair,
+            (_, _, 2, _) => PokerScore::OnePair,
+            _ => PokerScore::HighCard,
+        };
+        let key = tiebreak
+            .iter()
+            .fold(score as u32, |key, rank| key << 4 | rank);
+        Ok(PokerHand { cards, score, key })
+    }
+
+    /// The strongest hand made from five of the given cards, as from two hole cards and five
+    /// community cards.
+    pub fn best_of(cards: &[SuitedCard]) -> Result<Self, String> {
+        if cards.len() < 5 {
+            return Err(format!(
+                "Can not make a hand from only {} cards",
+                cards.len()
+            ));
+        }
+        let mut best: Option<PokerHand> = None;
+        let n = cards.len();
+        for a in 0..n {
+            for b in a + 1..n {
+                for c in b + 1..n {
+                    for d in c + 1..n {
+                        for e in d + 1..n {
+                            let hand =
+                                PokerHand::new([cards[a], cards[b], cards[c], cards[d], cards[e]])?;
+                            if best.is_none_or(|best| hand > best) {
+                                best = Some(hand);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(best.expect("Expected at least one hand of five"))
+    }
+
+    pub fn cards(&self) -> &[SuitedCard; 5] {
+        &self.cards
+    }
+
+    pub fn score(&self) -> PokerScore {
+        self.score
+    }
+
+    pub fn sort_key(&self) -> u32 {
+        self.key
+    }
+}
+
+impl PartialEq for PokerHand {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for PokerHand {}
+
+impl PartialOrd for PokerHand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PokerHand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// Reads five whitespace separated suited cards, such as `AS KD TD 9C 2H`.
+impl FromStr for PokerHand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cards = parse_suited_cards(s)?;
+        let len = cards.len();
+        let cards: [SuitedCard; 5] = cards
+            .try_into()
+            .map_err(|_| format!("Hand '{}' has {} cards, expected 5", s, len))?;
+        PokerHand::new(cards)
+    }
+}
+
+/// Reads whitespace separated suited cards.
+pub fn parse_suited_cards(value: &str) -> Result<Vec<SuitedCard>, String> {
+    value.split_whitespace().map(SuitedCard::from_str).collect()
+}
+
+#[cfg(test)]
+mod a_poker_hand {
+    use rstest::rstest;
+
+    use super::{parse_suited_cards, PokerHand, PokerScore};
+
+    fn hand(value: &str) -> PokerHand {
+        value.parse().unwrap()
+    }
+
+    #[rstest]
+    #[case("AS KS QS JS TS", PokerScore::StraightFlush)]
+    #[case("5D 4D 3D 2D AD", PokerScore::StraightFlush)]
+    #[case("9C 9D 9H 9S 2C", PokerScore::FourOfAKind)]
+    #[case("KC KD KH 2S 2C", PokerScore::FullHouse)]
+    #[case("2H 7H 9H JH KH", PokerScore::Flush)]
+    #[case("QS KS AS 2S 3S", PokerScore::Flush)]
+    #[case("TC JD QH KS AC", PokerScore::Straight)]
+    #[case("5H 4D 3C 2S AS", PokerScore::Straight)]
+    #[case("QS KD AC 2H 3S", PokerScore::HighCard)]
+    #[case("7C 7D 7H 2S 3C", PokerScore::ThreeOfAKind)]
+    #[case("7C 7D 3H 3S 2C", PokerScore::TwoPair)]
+    #[case("7C 7D 4H 3S 2C", PokerScore::OnePair)]
+    #[case("7C 5D 4H 3S 2C", PokerScore::HighCard)]
+    fn can_be_scored(#[case] value: &str, #[case] expected: PokerScore) {
+        assert_eq!(expected, hand(value).score());
+    }
+
+    #[rstest]
+    #[case("5H 4D 3C 2S AS", "6H 5D 4C 3S 2S")]
+    #[case("AH AD KC QS JS", "2H 2D 3C 3S 4S")]
+    #[case("QC QD QH AS AC", "KC KD KH 2S 2C")]
+    #[case("7C 7D 3H 3S 2C", "7C 7D 3H 3S 4C")]
+    #[case("TC JD QH KS AC", "2H 7H 9H JH KH")]
+    #[case("AH KH QH JH 9H", "TC JC QC KC AC")]
+    fn is_ordered_by_poker_rules(#[case] weaker: &str, #[case] stronger: &str) {
+        assert!(hand(weaker) < hand(stronger));
+    }
+
+    #[test]
+    fn ties_hands_differing_only_by_suit() {
+        let (a, b) = (hand("AH KD 9C 7S 2S"), hand("AS KC 9D 7H 2H"));
+        assert_eq!(std::cmp::Ordering::Equal, a.cmp(&b));
+        assert_eq!(a, b);
+        assert_ne!(a.cards(), b.cards());
+    }
+
+    #[rstest]
+    #[case("AS KS QS JS TS 2D 3C", PokerScore::StraightFlush)]
+    #[case("2H 2D 2C KS KD 3S 4H", PokerScore::FullHouse)]
+    #[case("2H 3D 4C 5S 9D JS AH", PokerScore::Straight)]
+    #[case("2H 4H 6H 8H TH JH 3C", PokerScore::Flush)]
+    #[case("AH KD 9C 7S 5S 3D 2C", PokerScore::HighCard)]
+    fn can_find_the_best_five_of_seven(#[case] value: &str, #[case] expected: PokerScore) {
+        let cards = parse_suited_cards(value).unwrap();
+        assert_eq!(expected, PokerHand::best_of(&cards).unwrap().score());
+    }
+
+    #[test]
+    fn keeps_the_best_kickers_of_seven() {
+        let cards = parse_suited_cards("AH KD 9C 7S 5S 3D 2C").unwrap();
+        assert_eq!(
+            hand("AH KD 9C 7S 5S").cards(),
+            PokerHand::best_of(&cards).unwrap().cards()
+        );
+    }
+
+    #[rstest]
+    #[case("AS KS QS JS")]
+    #[case("AS KS QS JS TS 9S")]
+    #[case("AS AS QS JS TS")]
+    #[case("AS KS QS JS 1S")]
+    fn rejects_invalid_hands(#[case] value: &str) {
+        assert!(value.parse::<PokerHand>().is_err());
+    }
+
+    #[test]
+    fn can_not_find_the_best_of_too_few_cards() {
+        let cards = parse_suited_cards("AS KS QS JS").unwrap();
+        assert!(PokerHand::best_of(&cards).is_err());
+    }
+}
@@ -0,0 +1,205 @@
+use std::fmt;
+
+use super::hand_score::HandScore;
+use super::Hand;
+use crate::card::Card;
+use crate::ruleset::Ruleset;
+
+/// A wildcard and the card it stands in for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Replacement {
+    /// Position of the wildcard in the hand, from 0.
+    pub position: usize,
+    pub wildcard: Card,
+    pub card: Card,
+}
+
+/// How a hand's wildcards are played to reach its category.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Substitution {
+    /// One replacement per wildcard, in the order they appear in the hand.
+    pub replacements: Vec<Replacement>,
+    /// The hand with every wildcard replaced, scored under the standard rules.
+    pub hand: Hand,
+}
+
+impl Substitution {
+    /// The substitution reaching the best category a hand can score under `ruleset`.
+    ///
+    /// Of the substitutions reaching it, picks the strongest hand under the standard rules, so
+    /// each wildcard in turn becomes the highest card that still allows the best category.
+    pub fn best(hand: &Hand, ruleset: &Ruleset) -> Self {
+        let mut occurences = [0_u8; Card::COUNT];
+        let mut wildcards = 0;
+        for card in hand.cards().iter().copied() {
+            match ruleset.is_wildcard(card) {
+                true => wildcards += 1,
+                false => occurences[card.index()] += 1,
+            }
+        }
+        let best = HandScore::from_counts(&occurences, wildcards);
+
+        let mut cards = hand.cards().to_vec();
+        let mut replacements = vec![];
+        for (position, wildcard) in hand.cards().iter().copied().enumerate() {
+            if !ruleset.is_wildcard(wildcard) {
+                continue;
+            }
+            wildcards -= 1;
+            // Card::ALL runs from strongest to weakest under the standard rules
+            let card = Card::ALL
+                .into_iter()
+                .find(|card| {
+                    occurences[card.index()] += 1;
+                    let reachable = HandScore::from_counts(&occurences, wildcards) == best;
+                    occurences[card.index()] -= 1;
+                    reachable
+                })
+                .expect("Some card always keeps the best category reachable");
+            occurences[card.index()] += 1;
+            cards[position] = card;
+            replacements.push(Replacement {
+                position,
+                wildcard,
+                card,
+            });
+        }
+        let hand = Hand::from_slice(&cards, &Ruleset::STANDARD)
+            .expect("Substituting cards keeps the hand's size");
+        Substitution { replacements, hand }
+    }
+
+    /// Whether the hand had any wildcards to replace.
+    pub fn is_empty(&self) -> bool {
+        self.replacements.is_empty()
+    }
+
+    /// Lists each replacement, such as `card 2 as K, card 3 as K`, with cards written in the
+    /// ruleset's alphabet.
+    pub fn to_symbols(&self, ruleset: &Ruleset) -> String {
+        self.replacements
+            .iter()
+            .map(|replacement| {
+                format!(
+                    "card {} as {}",
+                    replacement.position,
+                    ruleset.symbol(replacement.card)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Lists each replacement, such as `card 2 as K, card 3 as K`.
+impl fmt::Display for Substitution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_symbols(&Ruleset::STANDARD))
+    }
+}
+
+#[cfg(test)]
+mod a_substitution {
+    use rstest::rstest;
+
+    use super::{Replacement, Substitution};
+    use crate::card::Card;
+    use crate::hand::hand_score::HandScore;
+    use crate::hand::Hand;
+    use crate::ruleset::Ruleset;
+
+    fn best(value: &str, ruleset: &Ruleset) -> Substitution {
+        Substitution::best(&Hand::parse(value, ruleset).unwrap(), ruleset)
+    }
+
+    #[rstest]
+    #[case("32T3K", "32T3K")]
+    #[case("T55J5", "T5555")]
+    #[case("KTJJT", "KTTTT")]
+    #[case("QQQJA", "QQQQA")]
+    #[case("JJJJJ", "AAAAA")]
+    #[case("2345J", "23455")]
+    #[case("22J33", "22333")]
+    #[case("J2J33", "32333")]
+    #[case("JJ2", "222")]
+    #[case("JJJJJ2", "222222")]
+    fn plays_each_joker_as_the_strongest_card_reaching_the_best_category(
+        #[case] value: &str,
+        #[case] expected: &str,
+    ) {
+        let substitution = best(value, &Ruleset::JOKER);
+        assert_eq!(expected, substitution.hand.to_string());
+        assert_eq!(
+            HandScore::with_ruleset(substitution.hand.cards(), &Ruleset::JOKER),
+            substitution.hand.score()
+        );
+        assert_eq!(
+            Hand::parse(value, &Ruleset::JOKER).unwrap().score(),
+            substitution.hand.score()
+        );
+    }
+
+    #[test]
+    fn lists_each_replacement() {
+        let substitution = best("KTJJT", &Ruleset::JOKER);
+        assert_eq!(
+            vec![
+                Replacement {
+                    position: 2,
+                    wildcard: Card::Jack,
+                    card: Card::Ten
+                },
+                Replacement {
+                    position: 3,
+                    wildcard: Card::Jack,
+                    card: Card::Ten
+                }
+            ],
+            substitution.replacements
+        );
+        assert_eq!("card 2 as T, card 3 as T", substitution.to_string());
+    }
+
+    #[test]
+    fn lists_each_replacement_in_its_rulesets_alphabet() {
+        let ruleset: Ruleset = "alphabet: AKQWX98765432\nwildcards: W".parse().unwrap();
+        let substitution = best("KXWWX", &ruleset);
+        assert_eq!(
+            "card 2 as X, card 3 as X",
+            substitution.to_symbols(&ruleset)
+        );
+        assert_eq!("card 2 as T, card 3 as T", substitution.to_string());
+    }
+
+    #[test]
+    fn replaces_nothing_without_wildcards() {
+        let substitution = best("KTJJT", &Ruleset::STANDARD);
+        assert!(substitution.is_empty());
+        assert_eq!("KTJJT", substitution.hand.to_string());
+        assert_eq!("", substitution.to_string());
+    }
+
+    #[test]
+    fn matches_an_exhaustive_search_over_every_hand_with_two_jokers() {
+        let others: Vec<Card> = Card::ALL
+            .into_iter()
+            .filter(|card| *card != Card::Jack)
+            .collect();
+        for a in others.iter().copied() {
+            for b in others.iter().copied() {
+                for c in others.iter().copied() {
+                    let cards = [a, Card::Jack, b, Card::Jack, c];
+                    let hand = Hand::with_ruleset(cards, &Ruleset::JOKER);
+                    let exhaustive = Card::ALL
+                        .into_iter()
+                        .flat_map(|x| Card::ALL.into_iter().map(move |y| [a, x, b, y, c]))
+                        .map(Hand::new)
+                        .filter(|candidate| candidate.score() == hand.score())
+                        .max()
+                        .unwrap();
+                    assert_eq!(exhaustive, Substitution::best(&hand, &Ruleset::JOKER).hand);
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,7 @@
 pub mod card;
+pub mod equity;
 pub mod hand;
 pub mod play;
+pub mod report;
+pub mod ruleset;
+pub mod tournament;
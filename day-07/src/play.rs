@@ -1,6 +1,14 @@
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
 use super::hand::Hand;
+use crate::card::Card;
+use crate::ruleset::Ruleset;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Play {
     hand: Hand,
     bid: u32,
@@ -18,11 +26,113 @@ impl Play {
     pub fn bid(&self) -> &u32 {
         &self.bid
     }
+
+    /// Reads a hand of any size and its bid, separated by any whitespace.
+    ///
+    /// Never panics, whatever the input, so that lines can come straight from a file.
+    pub fn parse(value: &str, ruleset: &Ruleset) -> Result<Self, PlayError> {
+        let mut parts = value.split_whitespace();
+        let hand = parts.next().ok_or(PlayError::MissingHand)?;
+        let bid = parts.next().ok_or_else(|| PlayError::MissingBid {
+            hand: hand.to_string(),
+        })?;
+        if let Some(extra) = parts.next() {
+            return Err(PlayError::UnexpectedInput {
+                extra: extra.to_string(),
+            });
+        }
+
+        let length = hand.chars().count();
+        if length > Hand::MAX_SIZE {
+            return Err(PlayError::HandLength { length });
+        }
+        let cards = hand
+            .chars()
+            .enumerate()
+            .map(|(position, symbol)| {
+                ruleset
+                    .card(symbol)
+                    .map_err(|_| PlayError::UnknownCard { symbol, position })
+            })
+            .collect::<Result<Vec<Card>, PlayError>>()?;
+        let hand =
+            Hand::from_slice(&cards, ruleset).map_err(|_| PlayError::HandLength { length })?;
+
+        let bid = bid.parse::<u32>().map_err(|reason| PlayError::BadBid {
+            bid: bid.to_string(),
+            reason,
+        })?;
+        Ok(Play::new(hand, bid))
+    }
+}
+
+/// Why a line could not be read as a play.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayError {
+    /// The line is blank.
+    MissingHand,
+    MissingBid {
+        hand: String,
+    },
+    /// More follows the bid.
+    UnexpectedInput {
+        extra: String,
+    },
+    /// The hand does not hold 1 to [`Hand::MAX_SIZE`] cards.
+    HandLength {
+        length: usize,
+    },
+    /// A character that names no card, at its position in the hand, from 0.
+    UnknownCard {
+        symbol: char,
+        position: usize,
+    },
+    BadBid {
+        bid: String,
+        reason: ParseIntError,
+    },
+}
+
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayError::MissingHand => write!(f, "Play has no hand"),
+            PlayError::MissingBid { hand } => {
+                write!(f, "Play '{}' has no bid after its hand", hand)
+            }
+            PlayError::UnexpectedInput { extra } => {
+                write!(f, "Play has '{}' after its bid", extra)
+            }
+            PlayError::HandLength { length } => write!(
+                f,
+                "Hand of {} cards is not between 1 and {} cards",
+                length,
+                Hand::MAX_SIZE
+            ),
+            PlayError::UnknownCard { symbol, position } => write!(
+                f,
+                "Card can not be created from char {:?} at position {}",
+                symbol, position
+            ),
+            PlayError::BadBid { bid, reason } => {
+                write!(f, "Failed to parse bid {}, {}", bid, reason)
+            }
+        }
+    }
+}
+
+/// Sum of each bid multiplied by its play's rank, from 1 for the weakest hand.
+pub fn total_winnings(mut plays: Vec<Play>) -> u64 {
+    plays.sort();
+    (1..)
+        .zip(plays.iter())
+        .map(|(rank, play)| rank * *play.bid() as u64)
+        .sum()
 }
 
 impl PartialOrd for Play {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.hand.partial_cmp(&other.hand)
+        Some(self.cmp(other))
     }
 }
 
@@ -33,26 +143,158 @@ impl Ord for Play {
 }
 
 impl TryFrom<&str> for Play {
-    type Error = String;
+    type Error = PlayError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let hand: [char; 5] = match value[0..5].chars().collect::<Vec<char>>().try_into() {
-            Ok(hand) => hand,
-            Err(err) => {
-                return Err(format!(
-                    "Failed converting vector of chars {:?} into char array",
-                    err
-                ))
+        Play::parse(value, &Ruleset::STANDARD)
+    }
+}
+
+/// Reads a play under the standard rules.
+impl FromStr for Play {
+    type Err = PlayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Play::parse(s, &Ruleset::STANDARD)
+    }
+}
+
+/// Writes the hand with the standard alphabet, then the bid.
+impl fmt::Display for Play {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.hand, self.bid)
+    }
+}
+
+#[cfg(test)]
+mod a_play {
+    use rstest::rstest;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::{total_winnings, Play, PlayError};
+    use crate::card::Card;
+    use crate::hand::hand_score::HandScore;
+    use crate::hand::Hand;
+    use crate::ruleset::Ruleset;
+
+    #[rstest]
+    #[case("32T3K 765", 5, HandScore::OnePair, 765)]
+    #[case("KKK 1", 3, HandScore::ThreeOfAKind, 1)]
+    #[case("2323 40", 4, HandScore::TwoPair, 40)]
+    #[case("222333 7", 6, HandScore::FullHouse, 7)]
+    #[case("AAAAAAA 12", 7, HandScore::SevenOfAKind, 12)]
+    fn can_be_parsed_with_any_hand_size(
+        #[case] value: &str,
+        #[case] len: usize,
+        #[case] score: HandScore,
+        #[case] bid: u32,
+    ) {
+        let play = Play::try_from(value).unwrap();
+        assert_eq!(len, play.hand().len());
+        assert_eq!(score, play.hand().score());
+        assert_eq!(bid, *play.bid());
+    }
+
+    #[rstest]
+    #[case("32T3K\t765", "32T3K", 765)]
+    #[case("  32T3K   765  ", "32T3K", 765)]
+    #[case("KK677 \t 28\r", "KK677", 28)]
+    fn tolerates_any_whitespace(#[case] value: &str, #[case] hand: &str, #[case] bid: u32) {
+        assert_eq!(
+            Play::try_from(format!("{} {}", hand, bid).as_str()),
+            Play::try_from(value)
+        );
+    }
+
+    #[rstest]
+    #[case("", PlayError::MissingHand)]
+    #[case(" \t ", PlayError::MissingHand)]
+    #[case("32T3K", PlayError::MissingBid { hand: "32T3K".to_string() })]
+    #[case("32T3K 765 1", PlayError::UnexpectedInput { extra: "1".to_string() })]
+    #[case("AKQJT98765 1", PlayError::HandLength { length: 10 })]
+    #[case("32T3X 765", PlayError::UnknownCard { symbol: 'X', position: 4 })]
+    #[case("3é2 765", PlayError::UnknownCard { symbol: 'é', position: 1 })]
+    #[case("🃏🃏🃏🃏🃏🃏🃏🃏 1", PlayError::HandLength { length: 8 })]
+    fn explains_invalid_plays(#[case] value: &str, #[case] expected: PlayError) {
+        assert_eq!(Err(expected), Play::try_from(value));
+    }
+
+    #[rstest]
+    #[case("32T3K x")]
+    #[case("32T3K -1")]
+    #[case("32T3K 4294967296")]
+    fn rejects_invalid_bids(#[case] value: &str) {
+        assert!(matches!(
+            Play::try_from(value),
+            Err(PlayError::BadBid { .. })
+        ));
+    }
+
+    #[test]
+    fn never_panics_on_random_input() {
+        const PIECES: [&str; 12] = [
+            "A", "K", "J", "2", "9", "0", " ", "\t", "\n", "é", "🃏", "\u{3000}",
+        ];
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..20_000 {
+            let length = rng.gen_range(0..16);
+            let value: String = (0..length)
+                .map(|_| match rng.gen_bool(0.9) {
+                    true => PIECES[rng.gen_range(0..PIECES.len())].to_string(),
+                    false => rng.gen::<char>().to_string(),
+                })
+                .collect();
+            for ruleset in [Ruleset::STANDARD, Ruleset::JOKER] {
+                if let Ok(play) = Play::parse(&value, &ruleset) {
+                    assert!(
+                        (1..=Hand::MAX_SIZE).contains(&play.hand().len()),
+                        "{:?}",
+                        value
+                    );
+                }
             }
-        };
-        let hand = match Hand::try_from(hand) {
-            Ok(hand) => hand,
-            Err(err) => return Err(err),
-        };
-        let bid = match value[6..].parse::<u32>() {
-            Ok(bid) => bid,
-            Err(err) => return Err(format!("Failed to parse bid {}, {}", &value[6..], err)),
-        };
-        Ok(Play::new(hand, bid))
+        }
+    }
+
+    #[rstest]
+    #[case(Ruleset::STANDARD)]
+    #[case(Ruleset::JOKER)]
+    fn round_trips_through_strings_and_json(#[case] ruleset: Ruleset) {
+        let mut rng = StdRng::seed_from_u64(48);
+        for _ in 0..1000 {
+            let cards: Vec<Card> = (0..rng.gen_range(1..=Hand::MAX_SIZE))
+                .map(|_| Card::ALL[rng.gen_range(0..Card::COUNT)])
+                .collect();
+            let hand = Hand::from_slice(&cards, &ruleset).unwrap();
+            let play = Play::new(hand, rng.gen());
+            let line = format!("{} {}", hand.to_symbols(&ruleset), play.bid());
+            assert_eq!(Ok(play), Play::parse(&line, &ruleset));
+            if ruleset == Ruleset::STANDARD {
+                assert_eq!(Ok(play), play.to_string().parse());
+            }
+            let json = serde_json::to_string(&play).unwrap();
+            assert_eq!(play, serde_json::from_str::<Play>(&json).unwrap());
+        }
+    }
+
+    #[test]
+    fn can_be_written_as_a_line_of_input() {
+        let play: Play = "KTJJT\t220".parse().unwrap();
+        assert_eq!("KTJJT 220", play.to_string());
+        assert_eq!(
+            r#"{"hand":{"cards":"KTJJT","ranks":[11,8,9,9,8],"wildcards":""},"bid":220}"#,
+            serde_json::to_string(&play).unwrap()
+        );
+    }
+
+    #[test]
+    fn totals_winnings_beyond_a_u32() {
+        let plays = ["22 4000000000", "33 4000000000", "AA 4000000000"]
+            .iter()
+            .map(|line| Play::parse(line, &Ruleset::STANDARD).unwrap())
+            .collect();
+        assert_eq!(6 * 4_000_000_000, total_winnings(plays));
     }
 }
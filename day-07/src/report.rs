@@ -0,0 +1,327 @@
+use std::fmt;
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::hand::hand_score::HandScore;
+use crate::play::Play;
+use crate::ruleset::Ruleset;
+
+/// What put a play above the play ranked just below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Decider {
+    /// The play ranked last, with nothing below it.
+    Lowest,
+    /// A better category.
+    Category,
+    /// The same category, decided by the card at this position, from 0.
+    Card(usize),
+    /// The same category and the same leading cards, decided by the hands' sizes.
+    Size,
+    /// An identical hand, ordered arbitrarily.
+    Tie,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RankedPlay {
+    /// From 1 for the weakest hand.
+    pub rank: u32,
+    /// The hand as written under the report's ruleset.
+    pub hand: String,
+    /// The hand with its wildcards replaced, when it has any.
+    pub played_as: Option<String>,
+    /// Which card each wildcard stands in for, such as `card 2 as T`, in the report's alphabet.
+    pub substitution: Option<String>,
+    pub bid: u32,
+    pub score: HandScore,
+    pub decided_by: Decider,
+    pub winnings: u64,
+}
+
+/// Every play with its rank and winnings, from the weakest hand up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub plays: Vec<RankedPlay>,
+    pub total_winnings: u64,
+}
+
+impl Report {
+    /// Ranks plays that were parsed with `ruleset`.
+    pub fn new(mut plays: Vec<Play>, ruleset: &Ruleset) -> Self {
+        plays.sort();
+        let mut ranked_plays = vec![];
+        for (i, play) in plays.iter().enumerate() {
+            let rank = i as u32 + 1;
+            let decided_by = match i.checked_sub(1).map(|below| &plays[below]) {
+                None => Decider::Lowest,
+                Some(below) => match play.hand().deciding_position(below.hand()) {
+                    Some(position) => Decider::Card(position),
+                    None if play.hand().score() != below.hand().score() => Decider::Category,
+                    None if play.hand().len() != below.hand().len() => Decider::Size,
+                    None => Decider::Tie,
+                },
+            };
+            let substitution = play.hand().best_substitution(ruleset);
+            let (played_as, substitution) = match substitution.is_empty() {
+                true => (None, None),
+                false => (
+                    Some(substitution.hand.to_symbols(ruleset)),
+                    Some(substitution.to_symbols(ruleset)),
+                ),
+            };
+            ranked_plays.push(RankedPlay {
+                rank,
+                hand: play.hand().to_symbols(ruleset),
+                played_as,
+                substitution,
+                bid: *play.bid(),
+                score: play.hand().score(),
+                decided_by,
+                winnings: rank as u64 * *play.bid() as u64,
+            });
+        }
+        let total_winnings = ranked_plays.iter().map(|play| play.winnings).sum();
+        Report {
+            plays: ranked_plays,
+            total_winnings,
+        }
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut table = format!(
+            "{:>6} {:<8} {:<9} {:>6} {:<14} {:<10} {:>12}\n",
+            "Rank", "Hand", "Played as", "Bid", "Score", "Decided by", "Winnings"
+        );
+        for play in self.plays.iter() {
+            _ = writeln!(
+                table,
+                "{:>6} {:<8} {:<9} {:>6} {:<14} {:<10} {:>12}",
+                play.rank,
+                play.hand,
+                play.played_as.as_deref().unwrap_or("-"),
+                play.bid,
+                play.score.to_string(),
+                play.decided_by.to_string(),
+                play.winnings
+            );
+        }
+        _ = writeln!(table, "Total winnings = {}", self.total_winnings);
+        table
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv =
+            String::from("rank,hand,played_as,substitution,bid,score,decided_by,winnings\n");
+        for play in self.plays.iter() {
+            let fields = [
+                play.rank.to_string(),
+                play.hand.clone(),
+                play.played_as.clone().unwrap_or_default(),
+                play.substitution.clone().unwrap_or_default(),
+                play.bid.to_string(),
+                play.score.to_string(),
+                play.decided_by.to_string(),
+                play.winnings.to_string(),
+            ];
+            let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            _ = writeln!(csv, "{}", fields.join(","));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|err| format!("Failed to write report as JSON, {}", err))
+    }
+}
+
+/// Quotes a field holding a comma, quote or line break, doubling its quotes, as rulesets can
+/// write cards with any of them.
+fn csv_field(value: &str) -> String {
+    match value.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_string(),
+    }
+}
+
+impl fmt::Display for Decider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Decider::Lowest => write!(f, "lowest"),
+            Decider::Category => write!(f, "category"),
+            Decider::Card(position) => write!(f, "card {}", position),
+            Decider::Size => write!(f, "size"),
+            Decider::Tie => write!(f, "tie"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod a_report {
+    use super::{Decider, Report};
+    use crate::hand::hand_score::HandScore;
+    use crate::play::Play;
+    use crate::ruleset::Ruleset;
+
+    const EXAMPLE: &str = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
+
+    fn report(ruleset: &Ruleset) -> Report {
+        let plays = EXAMPLE
+            .lines()
+            .map(|line| Play::parse(line, ruleset).unwrap())
+            .collect();
+        Report::new(plays, ruleset)
+    }
+
+    #[test]
+    fn ranks_every_play() {
+        let report = report(&Ruleset::STANDARD);
+        let hands: Vec<&str> = report.plays.iter().map(|play| play.hand.as_str()).collect();
+        assert_eq!(vec!["32T3K", "KTJJT", "KK677", "T55J5", "QQQJA"], hands);
+        let ranks: Vec<u32> = report.plays.iter().map(|play| play.rank).collect();
+        assert_eq!(vec![1, 2, 3, 4, 5], ranks);
+        assert_eq!(HandScore::TwoPair, report.plays[1].score);
+        assert_eq!(28 * 3, report.plays[2].winnings);
+        assert_eq!(6440, report.total_winnings);
+    }
+
+    #[test]
+    fn records_what_decided_each_rank() {
+        let report = report(&Ruleset::STANDARD);
+        let decided_by: Vec<Decider> = report.plays.iter().map(|play| play.decided_by).collect();
+        assert_eq!(
+            vec![
+                Decider::Lowest,
+                Decider::Category,
+                Decider::Card(1),
+                Decider::Category,
+                Decider::Card(0)
+            ],
+            decided_by
+        );
+    }
+
+    #[test]
+    fn follows_its_ruleset() {
+        let report = report(&Ruleset::JOKER);
+        let hands: Vec<&str> = report.plays.iter().map(|play| play.hand.as_str()).collect();
+        assert_eq!(vec!["32T3K", "KK677", "T55J5", "QQQJA", "KTJJT"], hands);
+        assert_eq!(5905, report.total_winnings);
+    }
+
+    #[test]
+    fn shows_how_jokers_were_played() {
+        let report = report(&Ruleset::JOKER);
+        let promoted = &report.plays[4];
+        assert_eq!("KTJJT", promoted.hand);
+        assert_eq!(Some("KTTTT"), promoted.played_as.as_deref());
+        assert_eq!(
+            Some("card 2 as T, card 3 as T"),
+            promoted.substitution.as_deref()
+        );
+        assert_eq!(HandScore::FourOfAKind, promoted.score);
+        assert_eq!(None, report.plays[0].played_as);
+
+        let csv = report.to_csv();
+        assert_eq!(
+            "5,KTJJT,KTTTT,\"card 2 as T, card 3 as T\",220,FourOfAKind,card 0,1100",
+            csv.lines().nth(5).unwrap()
+        );
+        let table = report.to_table();
+        assert!(table
+            .lines()
+            .nth(5)
+            .unwrap()
+            .starts_with("     5 KTJJT    KTTTT"));
+    }
+
+    #[test]
+    fn shows_how_jokers_were_played_in_its_rulesets_alphabet() {
+        let ruleset: Ruleset = "alphabet: AKQWX98765432\norder: AKQX98765432W\nwildcards: W"
+            .parse()
+            .unwrap();
+        let plays = vec![Play::parse("KXWWX 220", &ruleset).unwrap()];
+        let report = Report::new(plays, &ruleset);
+        let promoted = &report.plays[0];
+        assert_eq!("KXWWX", promoted.hand);
+        assert_eq!(Some("KXXXX"), promoted.played_as.as_deref());
+        assert_eq!(
+            Some("card 2 as X, card 3 as X"),
+            promoted.substitution.as_deref()
+        );
+    }
+
+    #[test]
+    fn records_when_hand_sizes_decided_a_rank() {
+        let plays = ["KKA2 2", "KKA 1", "KK2A 3", "KKA 4"]
+            .iter()
+            .map(|line| Play::parse(line, &Ruleset::STANDARD).unwrap())
+            .collect();
+        let report = Report::new(plays, &Ruleset::STANDARD);
+        let ranked: Vec<(&str, Decider)> = report
+            .plays
+            .iter()
+            .map(|play| (play.hand.as_str(), play.decided_by))
+            .collect();
+        assert_eq!(
+            vec![
+                ("KK2A", Decider::Lowest),
+                ("KKA", Decider::Card(2)),
+                ("KKA", Decider::Tie),
+                ("KKA2", Decider::Size)
+            ],
+            ranked
+        );
+    }
+
+    #[test]
+    fn can_be_exported_as_a_table() {
+        let table = report(&Ruleset::STANDARD).to_table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(7, lines.len());
+        assert!(lines[0].starts_with("  Rank Hand"));
+        assert_eq!(
+            "     3 KK677    -             28 TwoPair        card 1               84",
+            lines[3]
+        );
+        assert_eq!("Total winnings = 6440", lines[6]);
+    }
+
+    #[test]
+    fn can_be_exported_as_csv() {
+        let csv = report(&Ruleset::STANDARD).to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            "rank,hand,played_as,substitution,bid,score,decided_by,winnings",
+            lines[0]
+        );
+        assert_eq!("1,32T3K,,,765,OnePair,lowest,765", lines[1]);
+        assert_eq!("5,QQQJA,,,483,ThreeOfAKind,card 0,2415", lines[5]);
+    }
+
+    #[test]
+    fn escapes_csv_fields_holding_separators_or_quotes() {
+        let ruleset: Ruleset = "alphabet: AKQ,\"98765432\norder: AKQ\"98765432,\nwildcards: ,"
+            .parse()
+            .unwrap();
+        let plays = vec![Play::parse("K\",,\" 220", &ruleset).unwrap()];
+        let csv = Report::new(plays, &ruleset).to_csv();
+        assert_eq!(
+            r#"1,"K"",,""","K""""""""","card 2 as "", card 3 as """,220,FourOfAKind,lowest,220"#,
+            csv.lines().nth(1).unwrap()
+        );
+    }
+
+    #[test]
+    fn can_be_exported_as_json() {
+        let json = report(&Ruleset::STANDARD).to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(6440, value["total_winnings"]);
+        assert_eq!("KTJJT", value["plays"][1]["hand"]);
+        assert_eq!("TwoPair", value["plays"][1]["score"]);
+        assert_eq!("category", value["plays"][1]["decided_by"]);
+        assert_eq!(1, value["plays"][2]["decided_by"]["card"]);
+        assert!(value["plays"][2]["played_as"].is_null());
+    }
+}
@@ -0,0 +1,282 @@
+use std::str::FromStr;
+
+use crate::card::Card;
+
+/// How cards are written, how they rank against each other and which of them are wild.
+///
+/// Ruleset files hold `key: value` lines, with blank lines and `#` comments ignored:
+///
+/// ```text
+/// # The characters for Ace, King, Queen, Jack, Ten and Nine down to Two
+/// alphabet: AKQJT98765432
+/// # Strongest first
+/// order: AKQT98765432J
+/// wildcards: J
+/// ```
+///
+/// Every key is optional. The alphabet defaults to the standard one, the order to the alphabet's
+/// and there are no wildcards by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ruleset {
+    /// The character naming each card, indexed by `Card::index`.
+    symbols: [char; Card::COUNT],
+    /// The strength of each card, indexed by `Card::index`. Higher ranks beat lower ones.
+    ranks: [u8; Card::COUNT],
+    /// Cards that stand in for whichever card makes the strongest hand.
+    wildcards: [bool; Card::COUNT],
+}
+
+const STANDARD_SYMBOLS: [char; Card::COUNT] = [
+    'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2',
+];
+
+impl Ruleset {
+    /// Part 1 rules, Aces high and no wildcards.
+    pub const STANDARD: Ruleset = Ruleset {
+        symbols: STANDARD_SYMBOLS,
+        ranks: [12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+        wildcards: [false; Card::COUNT],
+    };
+
+    /// Part 2 rules, where Jacks are wild Jokers but rank below every other card.
+    pub const JOKER: Ruleset = Ruleset {
+        symbols: STANDARD_SYMBOLS,
+        ranks: [12, 11, 10, 0, 9, 8, 7, 6, 5, 4, 3, 2, 1],
+        wildcards: [
+            false, false, false, true, false, false, false, false, false, false, false, false,
+            false,
+        ],
+    };
+
+    pub fn card(&self, symbol: char) -> Result<Card, String> {
+        match self.symbols.iter().position(|other| *other == symbol) {
+            Some(index) => Ok(Card::ALL[index]),
+            None => Err(format!("Card can not be created from char {}", symbol)),
+        }
+    }
+
+    pub fn symbol(&self, card: Card) -> char {
+        self.symbols[card.index()]
+    }
+
+    pub fn rank(&self, card: Card) -> u8 {
+        self.ranks[card.index()]
+    }
+
+    pub fn is_wildcard(&self, card: Card) -> bool {
+        self.wildcards[card.index()]
+    }
+
+    /// A ruleset ranking the given cards as given, with the other cards taking the remaining
+    /// ranks in the standard order. Enough to rebuild a hand from the ranks of its cards.
+    pub fn completing(ranks: &[(Card, u8)], wildcards: &[Card]) -> Result<Self, String> {
+        let mut assigned: [Option<u8>; Card::COUNT] = [None; Card::COUNT];
+        let mut used = [false; Card::COUNT];
+        for (card, rank) in ranks.iter().copied() {
+            if rank as usize >= Card::COUNT {
+                return Err(format!("Rank {} is not below {}", rank, Card::COUNT));
+            }
+            match assigned[card.index()] {
+                Some(other) if other != rank => {
+                    return Err(format!("{:?} ranks both {} and {}", card, other, rank))
+                }
+                Some(_) => continue,
+                None if used[rank as usize] => {
+                    return Err(format!("{:?} shares rank {} with another card", card, rank))
+                }
+                None => {
+                    assigned[card.index()] = Some(rank);
+                    used[rank as usize] = true;
+                }
+            }
+        }
+
+        let mut ruleset = Ruleset::STANDARD;
+        let mut free = (0..Card::COUNT as u8)
+            .rev()
+            .filter(|rank| !used[*rank as usize]);
+        for card in Card::ALL {
+            ruleset.ranks[card.index()] = match assigned[card.index()] {
+                Some(rank) => rank,
+                None => free.next().expect("Every unassigned card has a free rank"),
+            };
+        }
+        for card in wildcards {
+            ruleset.wildcards[card.index()] = true;
+        }
+        Ok(ruleset)
+    }
+
+    /// Cards from strongest to weakest.
+    pub fn order(&self) -> [Card; Card::COUNT] {
+        let mut order = Card::ALL;
+        order.sort_by_key(|card| std::cmp::Reverse(self.rank(*card)));
+        order
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Ruleset::STANDARD
+    }
+}
+
+impl FromStr for Ruleset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ruleset = Ruleset::STANDARD;
+        let mut order = None;
+        let mut wildcards = None;
+        for line in s.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once(':').ok_or(format!(
+                "Ruleset line '{}' is not a 'key: value' pair",
+                line
+            ))?;
+            let value = value.trim();
+            match key.trim() {
+                "alphabet" => ruleset.symbols = parse_alphabet(value)?,
+                "order" => order = Some(value),
+                "wildcards" => wildcards = Some(value),
+                key => return Err(format!("Unknown ruleset key '{}'", key)),
+            }
+        }
+
+        if let Some(order) = order {
+            let cards = order
+                .chars()
+                .map(|symbol| ruleset.card(symbol))
+                .collect::<Result<Vec<Card>, String>>()?;
+            let mut seen = [false; Card::COUNT];
+            for card in cards.iter() {
+                if std::mem::replace(&mut seen[card.index()], true) {
+                    return Err(format!(
+                        "Order '{}' repeats '{}'",
+                        order,
+                        ruleset.symbol(*card)
+                    ));
+                }
+            }
+            if cards.len() != Card::COUNT {
+                return Err(format!(
+                    "Order '{}' ranks {} cards, expected all {}",
+                    order,
+                    cards.len(),
+                    Card::COUNT
+                ));
+            }
+            for (position, card) in cards.iter().enumerate() {
+                ruleset.ranks[card.index()] = (Card::COUNT - 1 - position) as u8;
+            }
+        }
+        if let Some(wildcards) = wildcards {
+            for symbol in wildcards.chars().filter(|symbol| !symbol.is_whitespace()) {
+                let card = ruleset.card(symbol)?;
+                ruleset.wildcards[card.index()] = true;
+            }
+        }
+        Ok(ruleset)
+    }
+}
+
+fn parse_alphabet(value: &str) -> Result<[char; Card::COUNT], String> {
+    let symbols: Vec<char> = value.chars().collect();
+    let symbols: [char; Card::COUNT] = symbols.try_into().map_err(|symbols: Vec<char>| {
+        format!(
+            "Alphabet '{}' has {} characters, expected {}",
+            value,
+            symbols.len(),
+            Card::COUNT
+        )
+    })?;
+    for (i, symbol) in symbols.iter().enumerate() {
+        if symbol.is_whitespace() || symbols[..i].contains(symbol) {
+            return Err(format!("Alphabet '{}' can not use '{}'", value, symbol));
+        }
+    }
+    Ok(symbols)
+}
+
+#[cfg(test)]
+mod a_ruleset {
+    use rstest::rstest;
+
+    use super::Ruleset;
+    use crate::card::Card;
+
+    #[rstest]
+    #[case(Ruleset::STANDARD, Card::Jack, 9, false)]
+    #[case(Ruleset::STANDARD, Card::Two, 0, false)]
+    #[case(Ruleset::JOKER, Card::Jack, 0, true)]
+    #[case(Ruleset::JOKER, Card::Two, 1, false)]
+    #[case(Ruleset::JOKER, Card::Ace, 12, false)]
+    fn can_rank_cards(
+        #[case] ruleset: Ruleset,
+        #[case] card: Card,
+        #[case] rank: u8,
+        #[case] wildcard: bool,
+    ) {
+        assert_eq!(rank, ruleset.rank(card));
+        assert_eq!(wildcard, ruleset.is_wildcard(card));
+    }
+
+    #[test]
+    fn can_order_cards() {
+        let order: String = Ruleset::JOKER
+            .order()
+            .iter()
+            .map(|card| Ruleset::JOKER.symbol(*card))
+            .collect();
+        assert_eq!("AKQT98765432J", order);
+    }
+
+    #[test]
+    fn can_be_loaded_from_a_config() {
+        let config = "# Part 2\norder: AKQT98765432J\nwildcards: J\n";
+        assert_eq!(Ok(Ruleset::JOKER), config.parse::<Ruleset>());
+        assert_eq!(Ok(Ruleset::STANDARD), "".parse::<Ruleset>());
+    }
+
+    #[test]
+    fn can_rename_cards() {
+        let ruleset: Ruleset = "alphabet: AKQWT98765432\nwildcards: W".parse().unwrap();
+        assert_eq!(Ok(Card::Jack), ruleset.card('W'));
+        assert!(ruleset.card('J').is_err());
+        assert!(ruleset.is_wildcard(Card::Jack));
+        assert_eq!(Ruleset::STANDARD.rank(Card::Jack), ruleset.rank(Card::Jack));
+    }
+
+    #[test]
+    fn can_be_completed_from_the_ranks_of_some_cards() {
+        let ruleset =
+            Ruleset::completing(&[(Card::Jack, 0), (Card::Two, 1)], &[Card::Jack]).unwrap();
+        assert_eq!(Ruleset::JOKER, ruleset);
+        let ruleset = Ruleset::completing(&[(Card::Ace, 12), (Card::Ace, 12)], &[]).unwrap();
+        assert_eq!(Ruleset::STANDARD, ruleset);
+    }
+
+    #[rstest]
+    #[case(&[(Card::Ace, 13)])]
+    #[case(&[(Card::Ace, 12), (Card::Ace, 11)])]
+    #[case(&[(Card::Ace, 12), (Card::King, 12)])]
+    fn can_not_be_completed_from_inconsistent_ranks(#[case] ranks: &[(Card, u8)]) {
+        assert!(Ruleset::completing(ranks, &[]).is_err());
+    }
+
+    #[rstest]
+    #[case("order AKQ")]
+    #[case("colour: red")]
+    #[case("alphabet: AKQJT9876543")]
+    #[case("alphabet: AKQJT98765433")]
+    #[case("order: AKQJT9876543")]
+    #[case("order: AKQJT98765433")]
+    #[case("order: AKQJT9876543X")]
+    #[case("wildcards: X")]
+    fn rejects_invalid_configs(#[case] config: &str) {
+        assert!(config.parse::<Ruleset>().is_err());
+    }
+}
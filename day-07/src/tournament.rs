@@ -0,0 +1,399 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::equity::Deck;
+use crate::hand::Hand;
+use crate::play::{total_winnings, Play};
+use crate::ruleset::Ruleset;
+
+/// Hands dealt in a row that were all dealt before, after which a tournament gives up.
+const MAX_REDEALS: usize = 1000;
+
+/// How bids are drawn.
+///
+/// Written as `constant:<bid>`, `uniform:<min>-<max>` or `exponential:<mean>`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BidDistribution {
+    Constant(u32),
+    /// Every bid from `min` to `max` equally likely.
+    Uniform {
+        min: u32,
+        max: u32,
+    },
+    /// Mostly small bids with a long tail, rounded and at least 1.
+    Exponential {
+        mean: f64,
+    },
+}
+
+/// What to deal for a tournament.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Config {
+    pub plays: usize,
+    pub hand_size: usize,
+    /// Each hand is dealt from a full copy of the deck.
+    pub deck: Deck,
+    pub bids: BidDistribution,
+}
+
+/// Plays with distinct hands, so that every ruleset ranks them in exactly one order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tournament {
+    /// Plays in the order they were dealt, scored under the standard rules.
+    pub plays: Vec<Play>,
+}
+
+/// Total winnings over many tournaments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub tournaments: usize,
+    pub mean: f64,
+    /// The sample variance, 0 for a single tournament.
+    pub variance: f64,
+    pub min: u64,
+    pub max: u64,
+}
+
+impl BidDistribution {
+    pub fn sample(&self, rng: &mut impl Rng) -> u32 {
+        match *self {
+            BidDistribution::Constant(bid) => bid,
+            BidDistribution::Uniform { min, max } => rng.gen_range(min..=max),
+            BidDistribution::Exponential { mean } => {
+                let uniform: f64 = rng.gen();
+                (-mean * (1.0 - uniform).ln())
+                    .round()
+                    .clamp(1.0, u32::MAX as f64) as u32
+            }
+        }
+    }
+}
+
+/// Bids like the puzzle input's, from 1 to 1000.
+impl Default for BidDistribution {
+    fn default() -> Self {
+        BidDistribution::Uniform { min: 1, max: 1000 }
+    }
+}
+
+impl FromStr for BidDistribution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, parameters) = s
+            .split_once(':')
+            .ok_or(format!("Bids '{}' are not a 'kind:parameters' pair", s))?;
+        let parse_bid = |value: &str| {
+            value
+                .trim()
+                .parse::<u32>()
+                .map_err(|err| format!("Failed to parse bid {} in '{}', {}", value, s, err))
+        };
+        match kind.trim() {
+            "constant" => Ok(BidDistribution::Constant(parse_bid(parameters)?)),
+            "uniform" => {
+                let (min, max) = parameters
+                    .split_once('-')
+                    .ok_or(format!("Bids '{}' need a 'min-max' range", s))?;
+                let (min, max) = (parse_bid(min)?, parse_bid(max)?);
+                if min > max {
+                    return Err(format!("Bids '{}' have a minimum above their maximum", s));
+                }
+                Ok(BidDistribution::Uniform { min, max })
+            }
+            "exponential" => {
+                let mean = parameters
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|err| format!("Failed to parse mean in '{}', {}", s, err))?;
+                if !mean.is_finite() || mean <= 0.0 {
+                    return Err(format!("Bids '{}' need a positive mean", s));
+                }
+                Ok(BidDistribution::Exponential { mean })
+            }
+            kind => Err(format!("Unknown bid distribution '{}'", kind)),
+        }
+    }
+}
+
+/// A thousand hands of five from a deck of 52, like the puzzle input.
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            plays: 1000,
+            hand_size: 5,
+            deck: Deck::uniform(4),
+            bids: BidDistribution::default(),
+        }
+    }
+}
+
+impl Tournament {
+    /// Deals the same tournament for the same config and seed.
+    pub fn deal(config: &Config, seed: u64) -> Result<Self, String> {
+        if config.hand_size == 0 || config.hand_size > Hand::MAX_SIZE {
+            return Err(format!(
+                "Hand size {} is not between 1 and {}",
+                config.hand_size,
+                Hand::MAX_SIZE
+            ));
+        }
+        if config.deck.len() < config.hand_size as u64 {
+            return Err(format!(
+                "Deck has {} cards, {} are needed for a hand",
+                config.deck.len(),
+                config.hand_size
+            ));
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut dealt = HashSet::new();
+        let mut plays = Vec::with_capacity(config.plays);
+        let mut redeals = 0;
+        while plays.len() < config.plays {
+            let cards = config.deck.deal(&mut rng, config.hand_size);
+            let hand = Hand::from_slice(&cards, &Ruleset::STANDARD)?;
+            // Sort keys of hands of one size are distinct for distinct cards
+            if !dealt.insert(hand.sort_key()) {
+                redeals += 1;
+                if redeals == MAX_REDEALS {
+                    return Err(format!(
+                        "Could only deal {} distinct hands of {} from the deck",
+                        plays.len(),
+                        config.hand_size
+                    ));
+                }
+                continue;
+            }
+            redeals = 0;
+            plays.push(Play::new(hand, config.bids.sample(&mut rng)));
+        }
+        Ok(Tournament { plays })
+    }
+
+    /// The puzzle answer for the tournament's plays under `ruleset`.
+    pub fn total_winnings(&self, ruleset: &Ruleset) -> u64 {
+        let plays = self
+            .plays
+            .iter()
+            .map(|play| {
+                let hand = Hand::from_slice(play.hand().cards(), ruleset)
+                    .expect("Dealt hands have a valid size");
+                Play::new(hand, *play.bid())
+            })
+            .collect();
+        total_winnings(plays)
+    }
+
+    /// A puzzle input, with hands written in the ruleset's alphabet.
+    pub fn to_input(&self, ruleset: &Ruleset) -> String {
+        let mut input = String::new();
+        for play in self.plays.iter() {
+            _ = writeln!(input, "{} {}", play.hand().to_symbols(ruleset), play.bid());
+        }
+        input
+    }
+}
+
+impl Summary {
+    /// Summarises the total winnings of each tournament, or `None` without any.
+    pub fn new(totals: &[u64]) -> Option<Self> {
+        let min = *totals.iter().min()?;
+        let max = *totals.iter().max()?;
+        let tournaments = totals.len();
+        let mean = totals.iter().map(|total| *total as f64).sum::<f64>() / tournaments as f64;
+        let variance = match tournaments {
+            1 => 0.0,
+            _ => {
+                totals
+                    .iter()
+                    .map(|total| (*total as f64 - mean).powi(2))
+                    .sum::<f64>()
+                    / (tournaments - 1) as f64
+            }
+        };
+        Some(Summary {
+            tournaments,
+            mean,
+            variance,
+            min,
+            max,
+        })
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+}
+
+/// Deals `tournaments` tournaments and summarises their total winnings under each ruleset.
+///
+/// Each tournament is dealt with a seed drawn from `seed`, so the whole run can be repeated.
+pub fn simulate(
+    config: &Config,
+    tournaments: usize,
+    rulesets: &[Ruleset],
+    seed: u64,
+) -> Result<Vec<Summary>, String> {
+    if tournaments == 0 {
+        return Err("Can not simulate without any tournaments".to_string());
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut totals = vec![Vec::with_capacity(tournaments); rulesets.len()];
+    for _ in 0..tournaments {
+        let tournament = Tournament::deal(config, rng.gen())?;
+        for (ruleset, totals) in rulesets.iter().zip(totals.iter_mut()) {
+            totals.push(tournament.total_winnings(ruleset));
+        }
+    }
+    Ok(totals
+        .iter()
+        .map(|totals| Summary::new(totals).expect("Expected at least one tournament"))
+        .collect())
+}
+
+#[cfg(test)]
+mod a_tournament {
+    use rstest::rstest;
+
+    use super::{simulate, BidDistribution, Config, Summary, Tournament};
+    use crate::equity::Deck;
+    use crate::play::{total_winnings, Play};
+    use crate::ruleset::Ruleset;
+
+    fn config(plays: usize, hand_size: usize, bids: BidDistribution) -> Config {
+        Config {
+            plays,
+            hand_size,
+            bids,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn deals_the_same_tournament_for_the_same_seed() {
+        let config = Config::default();
+        assert_eq!(
+            Tournament::deal(&config, 7).unwrap(),
+            Tournament::deal(&config, 7).unwrap()
+        );
+        assert_ne!(
+            Tournament::deal(&config, 7).unwrap(),
+            Tournament::deal(&config, 8).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(5)]
+    #[case(7)]
+    fn deals_distinct_hands_from_the_deck(#[case] hand_size: usize) {
+        let bids = BidDistribution::Uniform { min: 10, max: 20 };
+        let tournament = Tournament::deal(&config(13, hand_size, bids), 1).unwrap();
+        assert_eq!(13, tournament.plays.len());
+        for (i, play) in tournament.plays.iter().enumerate() {
+            assert_eq!(hand_size, play.hand().len());
+            assert!((10..=20).contains(play.bid()));
+            assert!(tournament.plays[..i]
+                .iter()
+                .all(|other| other.hand().cards() != play.hand().cards()));
+        }
+    }
+
+    #[test]
+    fn deals_from_a_deck_without_listing_its_cards() {
+        let config = Config {
+            deck: "A=1000000,K=1000000,Q=1000000".parse().unwrap(),
+            ..config(20, 7, BidDistribution::default())
+        };
+        let tournament = Tournament::deal(&config, 2).unwrap();
+        assert_eq!(20, tournament.plays.len());
+    }
+
+    #[rstest]
+    #[case(Ruleset::STANDARD)]
+    #[case(Ruleset::JOKER)]
+    fn emits_inputs_that_score_their_expected_total(#[case] ruleset: Ruleset) {
+        let tournament = Tournament::deal(&config(1000, 5, BidDistribution::default()), 3).unwrap();
+        let input = tournament.to_input(&ruleset);
+        let plays: Vec<Play> = input
+            .lines()
+            .map(|line| Play::parse(line, &ruleset).unwrap())
+            .collect();
+        assert_eq!(1000, plays.len());
+        assert_eq!(tournament.total_winnings(&ruleset), total_winnings(plays));
+    }
+
+    #[test]
+    fn totals_every_rank_when_bids_are_constant() {
+        let tournament =
+            Tournament::deal(&config(100, 5, BidDistribution::Constant(2)), 0).unwrap();
+        assert_eq!(100 * 101, tournament.total_winnings(&Ruleset::STANDARD));
+        assert_eq!(100 * 101, tournament.total_winnings(&Ruleset::JOKER));
+    }
+
+    #[rstest]
+    #[case(config(14, 1, BidDistribution::default()))]
+    #[case(config(1, 0, BidDistribution::default()))]
+    #[case(config(1, 8, BidDistribution::default()))]
+    #[case(Config { deck: "A=2,K=2".parse::<Deck>().unwrap(), ..config(1, 5, BidDistribution::default()) })]
+    fn can_not_deal_impossible_tournaments(#[case] config: Config) {
+        assert!(Tournament::deal(&config, 0).is_err());
+    }
+
+    #[rstest]
+    #[case("constant:5", BidDistribution::Constant(5))]
+    #[case("uniform:1-1000", BidDistribution::Uniform { min: 1, max: 1000 })]
+    #[case("uniform: 7 - 7", BidDistribution::Uniform { min: 7, max: 7 })]
+    #[case("exponential:250", BidDistribution::Exponential { mean: 250.0 })]
+    fn can_read_bid_distributions(#[case] value: &str, #[case] expected: BidDistribution) {
+        assert_eq!(Ok(expected), value.parse());
+    }
+
+    #[rstest]
+    #[case("constant")]
+    #[case("constant:-1")]
+    #[case("uniform:9-1")]
+    #[case("uniform:9")]
+    #[case("exponential:0")]
+    #[case("exponential:NaN")]
+    #[case("normal:5")]
+    fn rejects_invalid_bid_distributions(#[case] value: &str) {
+        assert!(value.parse::<BidDistribution>().is_err());
+    }
+
+    #[test]
+    fn summarises_total_winnings() {
+        let summary = Summary::new(&[2, 4, 4, 4, 5, 5, 7, 9]).unwrap();
+        assert_eq!(8, summary.tournaments);
+        assert_eq!(5.0, summary.mean);
+        assert_eq!(32.0 / 7.0, summary.variance);
+        assert_eq!((2, 9), (summary.min, summary.max));
+        assert_eq!(None, Summary::new(&[]));
+    }
+
+    #[test]
+    fn simulates_tournaments_under_each_ruleset() {
+        let exponential = config(200, 5, BidDistribution::Exponential { mean: 100.0 });
+        let rulesets = [Ruleset::STANDARD, Ruleset::JOKER];
+        let summaries = simulate(&exponential, 20, &rulesets, 11).unwrap();
+        assert_eq!(
+            summaries,
+            simulate(&exponential, 20, &rulesets, 11).unwrap()
+        );
+        for summary in summaries {
+            assert_eq!(20, summary.tournaments);
+            assert!(summary.variance > 0.0);
+            assert!(summary.min as f64 <= summary.mean && summary.mean <= summary.max as f64);
+        }
+
+        let constant = config(50, 5, BidDistribution::Constant(3));
+        let summaries = simulate(&constant, 5, &rulesets, 11).unwrap();
+        assert!(summaries.iter().all(|summary| summary.variance == 0.0));
+        assert!(simulate(&exponential, 0, &rulesets, 11).is_err());
+    }
+}